@@ -0,0 +1,181 @@
+use std::time::{Duration, SystemTime};
+
+use error_stack::{Result, ResultExt};
+use futures::TryStreamExt;
+use opendal::{Entry, Metakey, Operator};
+use thiserror::Error;
+
+use crate::{
+    config::Service,
+    job::{build_operator, TEST_OBJECT_PREFIX},
+};
+
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct CleanupError(pub String);
+
+/// Delete test objects under the prefix of service, which were last modified
+/// before `older_than` if given. Returns paths of the matched objects, which
+/// are only listed but not deleted if `dry_run` is set.
+pub fn cleanup(
+    service: &Service,
+    dry_run: bool,
+    older_than: Option<Duration>,
+) -> Result<Vec<String>, CleanupError> {
+    let error = || CleanupError("failed to clean up test objects".to_string());
+    let operator = build_operator(service).change_context_lazy(error)?;
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .change_context_lazy(error)?;
+
+    runtime.block_on(async {
+        let entries: Vec<Entry> = operator
+            .lister_with("/")
+            .metakey(Metakey::Mode | Metakey::LastModified)
+            .await
+            .change_context_lazy(error)?
+            .try_collect()
+            .await
+            .change_context_lazy(error)?;
+
+        let mut entries: Vec<Entry> = entries
+            .into_iter()
+            .filter(|entry| entry.name().starts_with(TEST_OBJECT_PREFIX))
+            .collect();
+        if let Some(age) = older_than {
+            let now = SystemTime::now();
+            let modified = futures::future::try_join_all(
+                entries.iter().map(|entry| last_modified(&operator, entry)),
+            )
+            .await
+            .change_context_lazy(error)?;
+            entries = entries
+                .into_iter()
+                .zip(modified)
+                .filter(|(_, modified)| match modified {
+                    Some(modified) => now.duration_since(*modified).unwrap_or_default() >= age,
+                    // Keep objects of unknown age if an age filter is given
+                    None => false,
+                })
+                .map(|(entry, _)| entry)
+                .collect();
+        }
+        let (dirs, files): (Vec<Entry>, Vec<Entry>) = entries
+            .into_iter()
+            .partition(|entry| entry.metadata().is_dir());
+
+        let matched = dirs
+            .iter()
+            .chain(files.iter())
+            .map(|entry| entry.path().to_string())
+            .collect::<Vec<_>>();
+        if dry_run {
+            return Ok(matched);
+        }
+
+        operator
+            .remove(
+                files
+                    .into_iter()
+                    .map(|entry| entry.path().to_string())
+                    .collect(),
+            )
+            .await
+            .change_context_lazy(error)?;
+        futures::future::try_join_all(dirs.iter().map(|dir| operator.remove_all(dir.path())))
+            .await
+            .change_context_lazy(error)?;
+
+        Ok(matched)
+    })
+}
+
+/// Time an entry was last modified. Directories are as new as the newest
+/// object under them, since object storages keep no time of directories.
+async fn last_modified(operator: &Operator, entry: &Entry) -> opendal::Result<Option<SystemTime>> {
+    if !entry.metadata().is_dir() {
+        return Ok(entry.metadata().last_modified().map(Into::into));
+    }
+
+    let objects: Vec<Entry> = operator
+        .lister_with(entry.path())
+        .recursive(true)
+        .metakey(Metakey::LastModified)
+        .await?
+        .try_collect()
+        .await?;
+    Ok(objects
+        .iter()
+        // Some services return the listed directory itself
+        .filter(|object| object.path() != entry.path())
+        .filter_map(|object| object.metadata().last_modified())
+        .max()
+        .map(Into::into))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_test_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        for path in [
+            "oio-test-file",
+            "oio-test-dir/file",
+            "other-file",
+            "other-dir/oio-test-file",
+        ] {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, b"data").unwrap();
+        }
+        let service: Service = toml::from_str(&format!(
+            r#"
+            type = "fs"
+            endpoint = ""
+            bucket = ""
+            access_key = ""
+            secret_key = ""
+            prefix = "{}"
+            "#,
+            dir.path().display()
+        ))
+        .unwrap();
+        let remaining = || {
+            let mut names: Vec<String> = std::fs::read_dir(dir.path())
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .collect();
+            names.sort();
+            names
+        };
+
+        // Objects written recently are not older than an hour
+        let hour = Duration::from_secs(3600);
+        assert!(cleanup(&service, false, Some(hour)).unwrap().is_empty());
+
+        // Directories are as old as the newest object under them
+        std::fs::File::options()
+            .write(true)
+            .open(dir.path().join("oio-test-dir/file"))
+            .unwrap()
+            .set_modified(SystemTime::now() - 2 * hour)
+            .unwrap();
+        assert_eq!(
+            cleanup(&service, true, Some(hour)).unwrap(),
+            vec!["oio-test-dir/"]
+        );
+
+        let mut matched = cleanup(&service, true, None).unwrap();
+        matched.sort();
+        assert_eq!(matched, vec!["oio-test-dir/", "oio-test-file"]);
+        assert_eq!(remaining().len(), 4);
+
+        cleanup(&service, false, None).unwrap();
+        assert_eq!(remaining(), vec!["other-dir", "other-file"]);
+        assert!(dir.path().join("other-dir/oio-test-file").exists());
+    }
+}
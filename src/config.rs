@@ -16,13 +16,140 @@ pub struct Config {
     pub job: JobConfig,
 }
 
+/// Service section of a config file, which is all the cleanup command needs
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceConfig {
+    pub service: Service,
+}
+
 impl Config {
     pub fn validate(&self) -> Result<(), ConfigError> {
-        if self.job.file_size < 4096 {
+        self.job.file_size.validate()?;
+
+        if self.job.workload == Workload::RangeRead {
+            let Some(block_size) = self.job.block_size else {
+                bail!(ConfigError(
+                    "block_size is required by range_read workload".to_string()
+                ));
+            };
+            if block_size.bytes() == 0 || block_size.bytes() > self.job.file_size.min() {
+                bail!(ConfigError(
+                    "block_size must be in range (0, minimum of file_size]".to_string()
+                ));
+            }
+            if let Some(Size(0)) = self.job.stride {
+                bail!(ConfigError("stride must be greater than 0".to_string()));
+            }
+        }
+
+        if let Some(0) = self.job.num_objects {
             bail!(ConfigError(
-                "file_size must be greater or equal to 4096".to_string()
+                "num_objects must be greater than 0".to_string()
             ));
         }
+        if let Some(skew) = self.job.zipf_skew {
+            if skew.is_nan() || skew < 0.0 {
+                bail!(ConfigError(
+                    "zipf_skew must be greater or equal to 0".to_string()
+                ));
+            }
+        }
+
+        if let Some(0) = self.job.list_fanout {
+            bail!(ConfigError(
+                "list_fanout must be greater than 0".to_string()
+            ));
+        }
+        let fanout = self.job.list_fanout.unwrap_or(10);
+        let depth = self.job.list_depth.unwrap_or(0);
+        if fanout.checked_pow(depth).is_none() {
+            bail!(ConfigError(format!(
+                "list_fanout ^ list_depth must be at most {}",
+                u32::MAX
+            )));
+        }
+        if let Some(0) = self.job.list_page_size {
+            bail!(ConfigError(
+                "list_page_size must be greater than 0".to_string()
+            ));
+        }
+        if let Some(0) = self.job.delete_batch_size {
+            bail!(ConfigError(
+                "delete_batch_size must be greater than 0".to_string()
+            ));
+        }
+        if let Some(Size(0)) = self.job.part_size {
+            bail!(ConfigError("part_size must be greater than 0".to_string()));
+        }
+        if let Some(Size(0)) = self.job.write_size {
+            bail!(ConfigError("write_size must be greater than 0".to_string()));
+        }
+        if let Some(Size(0)) = self.job.read_size {
+            bail!(ConfigError("read_size must be greater than 0".to_string()));
+        }
+        if let Some(Size(0)) = self.job.read_chunk_size {
+            bail!(ConfigError(
+                "read_chunk_size must be greater than 0".to_string()
+            ));
+        }
+        if let Some(0) = self.job.read_concurrency {
+            bail!(ConfigError(
+                "read_concurrency must be greater than 0".to_string()
+            ));
+        }
+        if let Some(percentiles) = &self.job.percentiles {
+            if let Some(p) = percentiles.iter().find(|p| !(**p > 0.0 && **p <= 100.0)) {
+                bail!(ConfigError(format!(
+                    "percentiles must be greater than 0 and at most 100, got {}",
+                    p
+                )));
+            }
+        }
+        if self.job.interval == Some(Duration::ZERO) {
+            bail!(ConfigError("interval must be greater than 0".to_string()));
+        }
+        if let Some(precision) = self.job.histogram_precision {
+            if !(1..=5).contains(&precision) {
+                bail!(ConfigError(
+                    "histogram_precision must be between 1 and 5".to_string()
+                ));
+            }
+        }
+        if let Some(ratio) = self.job.compression_ratio {
+            if ratio.is_nan() || ratio < 1.0 {
+                bail!(ConfigError(
+                    "compression_ratio must be greater or equal to 1".to_string()
+                ));
+            }
+        }
+        // Zeros and mostly zero data are the same at many keys and offsets,
+        // so reads of wrong objects or ranges would pass verification
+        if self.job.verify.unwrap_or(false)
+            && self.job.payload.unwrap_or_default() != Payload::Random
+        {
+            bail!(ConfigError("verify requires random payload".to_string()));
+        }
+        if let Some(0) = self.job.part_concurrency {
+            bail!(ConfigError(
+                "part_concurrency must be greater than 0".to_string()
+            ));
+        }
+        if let Some(0) = self.job.num_upload_keys {
+            bail!(ConfigError(
+                "num_upload_keys must be greater than 0".to_string()
+            ));
+        }
+
+        if self.job.workload == Workload::Mixed {
+            let Some(mix) = &self.job.mix else {
+                bail!(ConfigError("mix is required by mixed workload".to_string()));
+            };
+            if mix.read == 0 && mix.write == 0 && mix.stat == 0 && mix.delete == 0 {
+                bail!(ConfigError(
+                    "at least one weight of mix must be greater than 0".to_string()
+                ));
+            }
+        }
 
         Ok(())
     }
@@ -57,11 +184,138 @@ pub struct JobConfig {
     pub num_jobs: Option<u32>,
     /// Workload for testing
     pub workload: Workload,
-    /// Size of file in bytes
-    pub file_size: u32,
+    /// Size of each file, either fixed or sampled from a distribution
+    pub file_size: FileSize,
     /// Maximum time to run the job
     #[serde(with = "humantime_serde")]
     pub run_time: Duration,
+    /// Time to run the job before `run_time`, whose operations are issued as
+    /// usual but not measured, e.g. to warm up connections
+    /// Default: 0s
+    #[serde(default, with = "humantime_serde")]
+    pub ramp_time: Option<Duration>,
+    /// Size of each ranged read in bytes, required by `range_read` workload
+    pub block_size: Option<Size>,
+    /// Offset pattern of ranged reads
+    /// Default: sequential
+    pub range_mode: Option<RangeMode>,
+    /// Distance in bytes between the start of two consecutive strided reads
+    /// Default: 2 * block_size
+    pub stride: Option<Size>,
+    /// Weights of operations, required by `mixed` workload
+    pub mix: Option<Mix>,
+    /// Number of objects prefilled for reading
+    /// Default: 1
+    pub num_objects: Option<u32>,
+    /// How to choose the object each read targets
+    /// Default: uniform
+    pub key_distribution: Option<KeyDistribution>,
+    /// Skew of zipfian key distribution, larger means hotter hot keys
+    /// Default: 1.0
+    pub zipf_skew: Option<f64>,
+    /// Which keys uploads write to
+    /// Default: unique
+    pub upload_keys: Option<UploadKeys>,
+    /// Number of keys each worker rotates through with `rotating` upload keys
+    /// Default: 16
+    pub num_upload_keys: Option<u32>,
+    /// When to delete objects written during the run
+    /// Default: always
+    pub cleanup: Option<Cleanup>,
+    /// Stat keys that do not exist instead of prefilled objects in `stat`
+    /// workload, which measures handling of 404
+    /// Default: false
+    pub stat_missing: Option<bool>,
+    /// Number of sub directories of each directory prefilled by `list` workload
+    /// Default: 10
+    pub list_fanout: Option<u32>,
+    /// Levels of nested directories prefilled by `list` workload, objects are
+    /// put directly under the listed directory if 0
+    /// Default: 0
+    pub list_depth: Option<u32>,
+    /// List recursively with one lister, or walk through directories level by
+    /// level otherwise
+    /// Default: true
+    pub list_recursive: Option<bool>,
+    /// Maximum number of entries returned by each list request
+    /// Default: 1000
+    pub list_page_size: Option<u32>,
+    /// Number of objects deleted at once by `batch_delete` workload
+    /// Default: 100
+    pub delete_batch_size: Option<u32>,
+    /// Size of each part of multipart uploads in bytes
+    /// Default: decided by the service
+    pub part_size: Option<Size>,
+    /// Number of parts of an upload uploaded concurrently
+    /// Default: 1
+    pub part_concurrency: Option<u32>,
+    /// Size of each write when writing an object, which is ignored by
+    /// multipart uploads that write part by part
+    /// Default: 4KiB
+    pub write_size: Option<Size>,
+    /// Size of the buffer downloads stream objects through, which bounds
+    /// memory used by each download
+    /// Default: 64KiB
+    pub read_size: Option<Size>,
+    /// Size of each range downloads fetch separately, which enables chunked
+    /// downloads fetching ranges of an object concurrently
+    /// Default: 8MiB if `read_concurrency` is set, streams the whole object
+    /// otherwise
+    pub read_chunk_size: Option<Size>,
+    /// Number of ranges of a chunked download fetched concurrently
+    /// Default: 1
+    pub read_concurrency: Option<u32>,
+    /// Content of written data
+    /// Default: random
+    pub payload: Option<Payload>,
+    /// Target compression ratio of compressible payload
+    /// Default: 2.0
+    pub compression_ratio: Option<f64>,
+    /// Seed of the generator of written data, the same seed writes the same
+    /// data to the same keys
+    /// Default: random
+    pub seed: Option<u64>,
+    /// Check data of reads against the data written to objects, which is
+    /// derived from object keys and requires random payload
+    /// Default: false
+    pub verify: Option<bool>,
+    /// How to check data of reads if `verify` is enabled
+    /// Default: bytes
+    pub verify_method: Option<VerifyMethod>,
+    /// Number of significant decimal digits kept by histograms of samples,
+    /// higher precision takes more memory
+    /// Default: 3
+    pub histogram_precision: Option<u8>,
+    /// Percentiles of samples in the report besides min and max, e.g.
+    /// `[50, 99, 99.9]`
+    /// Default: [50, 90, 99, 99.9, 99.99]
+    pub percentiles: Option<Vec<f64>>,
+    /// Length of each interval of the time series in the report
+    /// Default: 1s
+    #[serde(default, with = "humantime_serde")]
+    pub interval: Option<Duration>,
+    /// Keep running when an operation fails, counting failed operations in
+    /// the report instead of aborting the job
+    /// Default: false
+    pub continue_on_error: Option<bool>,
+}
+
+/// Weights of operations in `mixed` workload, e.g. `read = 70, write = 30`
+/// issues reads 70% of the time and writes 30% of the time
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Mix {
+    /// Weight of reading the whole object
+    #[serde(default)]
+    pub read: u32,
+    /// Weight of writing a new object
+    #[serde(default)]
+    pub write: u32,
+    /// Weight of stating an object
+    #[serde(default)]
+    pub stat: u32,
+    /// Weight of deleting an object written before
+    #[serde(default)]
+    pub delete: u32,
 }
 
 /// Service kind
@@ -130,6 +384,18 @@ impl Display for ServiceType {
 pub enum Workload {
     Download,
     Upload,
+    /// Read ranges of `block_size` from a single object
+    RangeRead,
+    /// Weighted mix of operations described by `mix`
+    Mixed,
+    /// Stat objects without reading them
+    Stat,
+    /// Enumerate all objects under a directory
+    List,
+    /// Delete prefilled objects one by one
+    Delete,
+    /// Delete prefilled objects in batches of `delete_batch_size`
+    BatchDelete,
 }
 
 impl Display for Workload {
@@ -137,6 +403,12 @@ impl Display for Workload {
         match self {
             Workload::Download => write!(f, "download"),
             Workload::Upload => write!(f, "upload"),
+            Workload::RangeRead => write!(f, "range_read"),
+            Workload::Mixed => write!(f, "mixed"),
+            Workload::Stat => write!(f, "stat"),
+            Workload::List => write!(f, "list"),
+            Workload::Delete => write!(f, "delete"),
+            Workload::BatchDelete => write!(f, "batch_delete"),
         }
     }
 }
@@ -148,6 +420,12 @@ impl TryFrom<&str> for Workload {
         match value {
             "download" => Ok(Workload::Download),
             "upload" => Ok(Workload::Upload),
+            "range_read" => Ok(Workload::RangeRead),
+            "mixed" => Ok(Workload::Mixed),
+            "stat" => Ok(Workload::Stat),
+            "list" => Ok(Workload::List),
+            "delete" => Ok(Workload::Delete),
+            "batch_delete" => Ok(Workload::BatchDelete),
             _ => bail!(ConfigError(format!("invalid workload: {}", value))),
         }
     }
@@ -172,6 +450,635 @@ impl<'a> Deserialize<'a> for Workload {
     }
 }
 
+/// Offset pattern of ranged reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RangeMode {
+    /// Read blocks one after another, wrapping around at the end of object
+    #[default]
+    Sequential,
+    /// Read blocks at random offsets
+    Random,
+    /// Read blocks separated by a fixed stride
+    Strided,
+}
+
+impl Display for RangeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RangeMode::Sequential => write!(f, "sequential"),
+            RangeMode::Random => write!(f, "random"),
+            RangeMode::Strided => write!(f, "strided"),
+        }
+    }
+}
+
+impl TryFrom<&str> for RangeMode {
+    type Error = Report<ConfigError>;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "sequential" => Ok(RangeMode::Sequential),
+            "random" => Ok(RangeMode::Random),
+            "strided" => Ok(RangeMode::Strided),
+            _ => bail!(ConfigError(format!("invalid range mode: {}", value))),
+        }
+    }
+}
+
+impl Serialize for RangeMode {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+impl<'a> Deserialize<'a> for RangeMode {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        RangeMode::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Distribution of keys chosen from a dataset of objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyDistribution {
+    /// Every object is equally likely to be chosen
+    #[default]
+    Uniform,
+    /// A few objects are chosen much more often than the others
+    Zipfian,
+    /// Objects are chosen one after another
+    Sequential,
+}
+
+impl Display for KeyDistribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyDistribution::Uniform => write!(f, "uniform"),
+            KeyDistribution::Zipfian => write!(f, "zipfian"),
+            KeyDistribution::Sequential => write!(f, "sequential"),
+        }
+    }
+}
+
+impl TryFrom<&str> for KeyDistribution {
+    type Error = Report<ConfigError>;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "uniform" => Ok(KeyDistribution::Uniform),
+            "zipfian" => Ok(KeyDistribution::Zipfian),
+            "sequential" => Ok(KeyDistribution::Sequential),
+            _ => bail!(ConfigError(format!("invalid key distribution: {}", value))),
+        }
+    }
+}
+
+impl Serialize for KeyDistribution {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+impl<'a> Deserialize<'a> for KeyDistribution {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        KeyDistribution::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Strategy of choosing keys written by uploads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UploadKeys {
+    /// Every upload writes a new key
+    #[default]
+    Unique,
+    /// Every worker overwrites its own set of keys in turn
+    Rotating,
+    /// All uploads of all workers overwrite the same key
+    Fixed,
+}
+
+impl Display for UploadKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UploadKeys::Unique => write!(f, "unique"),
+            UploadKeys::Rotating => write!(f, "rotating"),
+            UploadKeys::Fixed => write!(f, "fixed"),
+        }
+    }
+}
+
+impl TryFrom<&str> for UploadKeys {
+    type Error = Report<ConfigError>;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "unique" => Ok(UploadKeys::Unique),
+            "rotating" => Ok(UploadKeys::Rotating),
+            "fixed" => Ok(UploadKeys::Fixed),
+            _ => bail!(ConfigError(format!("invalid upload keys: {}", value))),
+        }
+    }
+}
+
+impl Serialize for UploadKeys {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+impl<'a> Deserialize<'a> for UploadKeys {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        UploadKeys::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// When to delete objects written during a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Cleanup {
+    /// Always delete written objects, even if the job failed
+    #[default]
+    Always,
+    /// Delete written objects unless the job failed, keeping them for
+    /// investigation
+    OnSuccess,
+    /// Never delete written objects
+    Never,
+}
+
+impl Display for Cleanup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Cleanup::Always => write!(f, "always"),
+            Cleanup::OnSuccess => write!(f, "on_success"),
+            Cleanup::Never => write!(f, "never"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Cleanup {
+    type Error = Report<ConfigError>;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "always" => Ok(Cleanup::Always),
+            "on_success" => Ok(Cleanup::OnSuccess),
+            "never" => Ok(Cleanup::Never),
+            _ => bail!(ConfigError(format!("invalid cleanup: {}", value))),
+        }
+    }
+}
+
+impl Serialize for Cleanup {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+impl<'a> Deserialize<'a> for Cleanup {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Cleanup::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Content of data written by uploads and prefills
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Payload {
+    /// All bytes are zero
+    Zeros,
+    /// Random bytes, which are incompressible
+    #[default]
+    Random,
+    /// Random bytes mixed with zeros, compressible by `compression_ratio`
+    Compressible,
+}
+
+impl Display for Payload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Payload::Zeros => write!(f, "zeros"),
+            Payload::Random => write!(f, "random"),
+            Payload::Compressible => write!(f, "compressible"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Payload {
+    type Error = Report<ConfigError>;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "zeros" => Ok(Payload::Zeros),
+            "random" => Ok(Payload::Random),
+            "compressible" => Ok(Payload::Compressible),
+            _ => bail!(ConfigError(format!("invalid payload: {}", value))),
+        }
+    }
+}
+
+impl Serialize for Payload {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+impl<'a> Deserialize<'a> for Payload {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Payload::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// How to check data of reads against the expected data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerifyMethod {
+    /// Compare byte by byte
+    #[default]
+    Bytes,
+    /// Compare CRC32C checksums
+    Crc32c,
+    /// Compare MD5 digests
+    Md5,
+}
+
+impl Display for VerifyMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyMethod::Bytes => write!(f, "bytes"),
+            VerifyMethod::Crc32c => write!(f, "crc32c"),
+            VerifyMethod::Md5 => write!(f, "md5"),
+        }
+    }
+}
+
+impl TryFrom<&str> for VerifyMethod {
+    type Error = Report<ConfigError>;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "bytes" => Ok(VerifyMethod::Bytes),
+            "crc32c" => Ok(VerifyMethod::Crc32c),
+            "md5" => Ok(VerifyMethod::Md5),
+            _ => bail!(ConfigError(format!("invalid verify_method: {}", value))),
+        }
+    }
+}
+
+impl Serialize for VerifyMethod {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+impl<'a> Deserialize<'a> for VerifyMethod {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let s = String::deserialize(deserializer)?;
+        VerifyMethod::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Size in bytes, which can be written as an integer of bytes or a string
+/// with unit, e.g. "4k", "64MiB" or "1.5GB". Units are case insensitive,
+/// "KB", "MB", "GB" and "TB" are multiples of 1000, while "KiB", "MiB", "GiB",
+/// "TiB" and their short forms "k", "m", "g", "t" are multiples of 1024.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Size(pub u64);
+
+const SIZE_UNITS: [(&str, u64); 13] = [
+    ("b", 1),
+    ("k", 1 << 10),
+    ("kib", 1 << 10),
+    ("kb", 1_000),
+    ("m", 1 << 20),
+    ("mib", 1 << 20),
+    ("mb", 1_000_000),
+    ("g", 1 << 30),
+    ("gib", 1 << 30),
+    ("gb", 1_000_000_000),
+    ("t", 1 << 40),
+    ("tib", 1 << 40),
+    ("tb", 1_000_000_000_000),
+];
+
+impl Size {
+    /// Get number of bytes
+    pub fn bytes(self) -> u64 {
+        self.0
+    }
+}
+
+impl Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (unit, multiple) in [
+            ("TiB", 1 << 40),
+            ("GiB", 1 << 30),
+            ("MiB", 1 << 20),
+            ("KiB", 1 << 10),
+        ] {
+            if self.0 >= multiple && self.0.is_multiple_of(multiple) {
+                return write!(f, "{}{}", self.0 / multiple, unit);
+            }
+        }
+        write!(f, "{}B", self.0)
+    }
+}
+
+impl TryFrom<&str> for Size {
+    type Error = Report<ConfigError>;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let error = || ConfigError(format!("invalid size: {}", value));
+
+        let value = value.trim();
+        let split = value
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(split);
+        let unit = unit.trim().to_lowercase();
+
+        let multiple = if unit.is_empty() {
+            1
+        } else {
+            match SIZE_UNITS.iter().find(|(name, _)| *name == unit) {
+                Some((_, multiple)) => *multiple,
+                None => bail!(error()),
+            }
+        };
+
+        if let Ok(number) = number.parse::<u64>() {
+            return number
+                .checked_mul(multiple)
+                .map(Size)
+                .ok_or_else(|| Report::new(error()));
+        }
+        let number: f64 = number.parse().map_err(|_| Report::new(error()))?;
+        let bytes = number * multiple as f64;
+        if !bytes.is_finite() || bytes >= u64::MAX as f64 {
+            bail!(error());
+        }
+        Ok(Size(bytes.round() as u64))
+    }
+}
+
+impl Serialize for Size {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+impl<'a> Deserialize<'a> for Size {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        struct SizeVisitor;
+
+        impl<'a> serde::de::Visitor<'a> for SizeVisitor {
+            type Value = Size;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "an integer of bytes or a string like \"64MiB\"")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(Size(v))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                u64::try_from(v)
+                    .map(Size)
+                    .map_err(|_| E::custom(format!("invalid size: {}", v)))
+            }
+
+            fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Size::try_from(v).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(SizeVisitor)
+    }
+}
+
+/// Minimum size of each file
+pub const MIN_FILE_SIZE: u64 = 4096;
+
+/// Size of each file, which is either a fixed size or a distribution that
+/// every written object samples its size from, e.g.
+///
+/// ```toml
+/// file_size = "64MiB"
+/// file_size = { distribution = "uniform", min = "4KiB", max = "1MiB" }
+/// file_size = { distribution = "buckets", buckets = [{ size = "4KiB", weight = 9 }, { size = "1GiB", weight = 1 }] }
+/// file_size = { distribution = "log_normal", mean = "1MiB", stddev = "4MiB" }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileSize {
+    Fixed(Size),
+    Distribution(SizeDistribution),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "distribution", rename_all = "snake_case")]
+pub enum SizeDistribution {
+    /// Uniformly distributed in [min, max]
+    Uniform { min: Size, max: Size },
+    /// One of the sizes, chosen by weight
+    Buckets { buckets: Vec<SizeBucket> },
+    /// Log-normally distributed with given mean and standard deviation,
+    /// samples less than `MIN_FILE_SIZE` are rounded up to it
+    LogNormal { mean: Size, stddev: Size },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SizeBucket {
+    pub size: Size,
+    pub weight: u32,
+}
+
+impl FileSize {
+    /// Minimum size that may be sampled
+    pub fn min(&self) -> u64 {
+        match self {
+            FileSize::Fixed(size) => size.bytes(),
+            FileSize::Distribution(SizeDistribution::Uniform { min, .. }) => min.bytes(),
+            FileSize::Distribution(SizeDistribution::Buckets { buckets }) => buckets
+                .iter()
+                .filter(|bucket| bucket.weight > 0)
+                .map(|bucket| bucket.size.bytes())
+                .min()
+                .unwrap_or_default(),
+            FileSize::Distribution(SizeDistribution::LogNormal { .. }) => MIN_FILE_SIZE,
+        }
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        match self {
+            FileSize::Distribution(SizeDistribution::Uniform { min, max }) if min > max => {
+                bail!(ConfigError(
+                    "min of file_size must be less or equal to max".to_string()
+                ));
+            }
+            FileSize::Distribution(SizeDistribution::Buckets { buckets })
+                if buckets.iter().all(|bucket| bucket.weight == 0) =>
+            {
+                bail!(ConfigError(
+                    "at least one weight of file_size buckets must be greater than 0".to_string()
+                ));
+            }
+            FileSize::Distribution(SizeDistribution::LogNormal { mean, .. })
+                if mean.bytes() == 0 =>
+            {
+                bail!(ConfigError(
+                    "mean of file_size must be greater than 0".to_string()
+                ));
+            }
+            _ => {}
+        }
+        if self.min() < MIN_FILE_SIZE {
+            bail!(ConfigError(format!(
+                "file_size must be greater or equal to {}",
+                MIN_FILE_SIZE
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for FileSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileSize::Fixed(size) => write!(f, "{}", size),
+            FileSize::Distribution(SizeDistribution::Uniform { min, max }) => {
+                write!(f, "uniform in [{}, {}]", min, max)
+            }
+            FileSize::Distribution(SizeDistribution::Buckets { buckets }) => {
+                write!(f, "buckets of")?;
+                for (i, bucket) in buckets.iter().enumerate() {
+                    let sep = if i == 0 { "" } else { "," };
+                    write!(f, "{} {} (weight {})", sep, bucket.size, bucket.weight)?;
+                }
+                Ok(())
+            }
+            FileSize::Distribution(SizeDistribution::LogNormal { mean, stddev }) => {
+                write!(f, "log-normal with mean {} and stddev {}", mean, stddev)
+            }
+        }
+    }
+}
+
+impl Serialize for FileSize {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            FileSize::Fixed(size) => size.serialize(serializer),
+            FileSize::Distribution(distribution) => distribution.serialize(serializer),
+        }
+    }
+}
+
+impl<'a> Deserialize<'a> for FileSize {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        struct FileSizeVisitor;
+
+        impl<'a> serde::de::Visitor<'a> for FileSizeVisitor {
+            type Value = FileSize;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a size or a table of size distribution")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(FileSize::Fixed(Size(v)))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                u64::try_from(v)
+                    .map(|v| FileSize::Fixed(Size(v)))
+                    .map_err(|_| E::custom(format!("invalid size: {}", v)))
+            }
+
+            fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Size::try_from(v).map(FileSize::Fixed).map_err(E::custom)
+            }
+
+            fn visit_map<A>(self, map: A) -> std::result::Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'a>,
+            {
+                SizeDistribution::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+                    .map(FileSize::Distribution)
+            }
+        }
+
+        deserializer.deserialize_any(FileSizeVisitor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_snapshot;
@@ -213,8 +1120,222 @@ mod tests {
         [job]
         num_jobs = 4
         workload = "download"
-        file_size = 1024
+        file_size = "1KiB"
         run_time = "1m"
         "###);
     }
+
+    #[test]
+    fn test_validate_range_read() {
+        let config = r#"
+        [service]
+        endpoint = ""
+        type = "fs"
+        bucket = ""
+        access_key = ""
+        secret_key = ""
+
+        [job]
+        run_time = "1s"
+        file_size = 65536
+        workload = "range_read"
+        "#;
+
+        let mut config: Config = toml::from_str(config).unwrap();
+        assert!(config.validate().is_err());
+
+        config.job.block_size = Some(Size(131072));
+        assert!(config.validate().is_err());
+
+        config.job.block_size = Some(Size::try_from("4k").unwrap());
+        config.job.range_mode = Some(RangeMode::Strided);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_percentiles() {
+        let config = r#"
+        [service]
+        endpoint = ""
+        type = "fs"
+        bucket = ""
+        access_key = ""
+        secret_key = ""
+
+        [job]
+        run_time = "1s"
+        file_size = 4096
+        workload = "download"
+        percentiles = [50, 99.9, 100]
+        "#;
+
+        let mut config: Config = toml::from_str(config).unwrap();
+        assert_eq!(config.job.percentiles, Some(vec![50.0, 99.9, 100.0]));
+        assert!(config.validate().is_ok());
+
+        config.job.percentiles = Some(vec![0.0]);
+        assert!(config.validate().is_err());
+
+        config.job.percentiles = Some(vec![99.0, 100.5]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_list_tree() {
+        let config = r#"
+        [service]
+        endpoint = ""
+        type = "fs"
+        bucket = ""
+        access_key = ""
+        secret_key = ""
+
+        [job]
+        run_time = "1s"
+        file_size = 4096
+        workload = "list"
+        list_fanout = 2
+        list_depth = 31
+        "#;
+
+        let mut config: Config = toml::from_str(config).unwrap();
+        assert!(config.validate().is_ok());
+
+        config.job.list_depth = Some(32);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_verify_payload() {
+        let config = r#"
+        [service]
+        endpoint = ""
+        type = "fs"
+        bucket = ""
+        access_key = ""
+        secret_key = ""
+
+        [job]
+        run_time = "1s"
+        file_size = 4096
+        workload = "download"
+        verify = true
+        "#;
+
+        let mut config: Config = toml::from_str(config).unwrap();
+        assert!(config.validate().is_ok());
+
+        for payload in [Payload::Zeros, Payload::Compressible] {
+            config.job.payload = Some(payload);
+            assert!(config.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_large_file_size() {
+        let config = r#"
+        [service]
+        endpoint = ""
+        type = "fs"
+        bucket = ""
+        access_key = ""
+        secret_key = ""
+
+        [job]
+        run_time = "1s"
+        # 50GiB
+        file_size = 53687091200
+        workload = "upload"
+        "#;
+
+        let config: Config = toml::from_str(config).unwrap();
+        assert_eq!(config.job.file_size.min(), 50 * 1024 * 1024 * 1024);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        let cases = [
+            ("4096", 4096),
+            ("4k", 4096),
+            ("4KiB", 4096),
+            ("4kb", 4000),
+            ("64MiB", 64 << 20),
+            ("1.5GB", 1_500_000_000),
+            ("0.5 g", 512 << 20),
+            ("2TiB", 2 << 40),
+        ];
+        for (input, bytes) in cases {
+            assert_eq!(Size::try_from(input).unwrap().bytes(), bytes, "{}", input);
+        }
+
+        assert!(Size::try_from("").is_err());
+        assert!(Size::try_from("4x").is_err());
+        assert!(Size::try_from("MiB").is_err());
+
+        assert_eq!(Size(64 << 20).to_string(), "64MiB");
+        assert_eq!(Size(1500).to_string(), "1500B");
+    }
+
+    #[test]
+    fn test_parse_file_size_distribution() {
+        let config = r#"
+        [service]
+        type = "fs"
+        endpoint = ""
+        bucket = ""
+        access_key = ""
+        secret_key = ""
+
+        [job]
+        workload = "upload"
+        run_time = "1m"
+
+        [job.file_size]
+        distribution = "buckets"
+        buckets = [
+            { size = "4KiB", weight = 90 },
+            { size = "1GiB", weight = 10 },
+        ]
+        "#;
+        let config: Config = toml::from_str(config).unwrap();
+        config.validate().unwrap();
+        assert_eq!(
+            config.job.file_size,
+            FileSize::Distribution(SizeDistribution::Buckets {
+                buckets: vec![
+                    SizeBucket {
+                        size: Size(4096),
+                        weight: 90
+                    },
+                    SizeBucket {
+                        size: Size(1 << 30),
+                        weight: 10
+                    },
+                ]
+            })
+        );
+        assert_eq!(config.job.file_size.min(), 4096);
+
+        let mut config = config;
+        config.job.file_size = FileSize::Distribution(SizeDistribution::Uniform {
+            min: Size(1 << 20),
+            max: Size(1 << 10),
+        });
+        assert!(config.validate().is_err());
+        config.job.file_size = FileSize::Distribution(SizeDistribution::Uniform {
+            min: Size(1 << 10),
+            max: Size(1 << 20),
+        });
+        assert!(config.validate().is_err());
+        config.job.file_size = FileSize::Distribution(SizeDistribution::LogNormal {
+            mean: Size(1 << 20),
+            stddev: Size(1 << 22),
+        });
+        config.validate().unwrap();
+
+        let serialized = toml::to_string(&config).unwrap();
+        let deserialized: Config = toml::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.job.file_size, config.job.file_size);
+    }
 }
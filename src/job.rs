@@ -1,12 +1,36 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
 use crate::{
-    config::{Config, Service, ServiceType, Workload},
-    sample::SampleSet,
+    config::{
+        Cleanup, Config, FileSize, KeyDistribution, Mix, Payload, RangeMode, Service, ServiceType,
+        Size, SizeDistribution, UploadKeys, VerifyMethod, Workload, MIN_FILE_SIZE,
+    },
+    report::{Metric, DEFAULT_PERCENTILES},
+    sample::{SampleSet, DEFAULT_PRECISION},
+    series::TimeSeries,
 };
 use bytes::Bytes;
-use error_stack::{Result, ResultExt};
-use opendal::Operator;
+use error_stack::{Report, Result, ResultExt};
+use futures::{AsyncReadExt, StreamExt, TryStreamExt};
+use opendal::{Buffer, ErrorKind, Operator};
+use rand::{
+    distributions::{Uniform, WeightedIndex},
+    prelude::Distribution,
+    rngs::StdRng,
+    Rng, SeedableRng,
+};
+use rand_distr::{LogNormal, Zipf};
 use thiserror::Error;
-use tokio::task::JoinHandle;
+use tokio::{sync::mpsc, task::JoinSet};
 
 #[derive(Debug, Error)]
 #[error("{0}")]
@@ -14,20 +38,279 @@ pub struct JobError(pub String);
 
 pub struct Job {
     config: Config,
+    /// Seed of the generator of written data
+    seed: u64,
+    /// Data written to objects, shared by all writes of the run
+    payload: PayloadBuffer,
+}
+
+/// Kind of operation issued against the object storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Operation {
+    Read,
+    Write,
+    Stat,
+    Delete,
+    List,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Read => write!(f, "read"),
+            Operation::Write => write!(f, "write"),
+            Operation::Stat => write!(f, "stat"),
+            Operation::Delete => write!(f, "delete"),
+            Operation::List => write!(f, "list"),
+        }
+    }
+}
+
+/// Numbers of operations completed by all workers in an interval of the run
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Counts {
+    /// Number of completed operations
+    pub ops: u64,
+    /// Bytes transferred
+    pub bytes: u64,
+    /// Number of failed operations, only counted if `continue_on_error` is
+    /// enabled
+    pub errors: u64,
+    /// Number of deleted objects, only counted by delete workloads
+    pub deleted: u64,
+}
+
+impl Counts {
+    /// Add counts of the same interval
+    fn merge(self, other: Counts) -> Self {
+        Self {
+            ops: self.ops + other.ops,
+            bytes: self.bytes + other.bytes,
+            errors: self.errors + other.errors,
+            deleted: self.deleted + other.deleted,
+        }
+    }
+}
+
+/// Sample sets of (Bandwidth, Latency, IOPS)
+#[derive(Debug, Clone)]
+pub struct OpStats {
+    /// Bandwidth in bytes/s
+    pub bandwidth: SampleSet,
+    /// Latency in microseconds
+    pub latency: SampleSet,
+    /// Counts of operations in each interval since the job started, see
+    /// `JobStats::interval`
+    pub counts: Vec<Counts>,
+    /// Listed entries per second, only sampled by list operations
+    pub entries: SampleSet,
+    /// Estimated pages listed per second, only sampled by list operations,
+    /// see `list`
+    pub estimated_pages: SampleSet,
+    /// Latency of each part in microseconds, only sampled by multipart uploads
+    pub part_latency: SampleSet,
+    /// Time to first byte in microseconds, only sampled by downloads
+    pub ttfb: SampleSet,
+    /// Number of reads whose data matched the written data, only counted if
+    /// `verify` is enabled
+    pub verified: u64,
+    /// Number of reads whose data did not match the written data
+    pub mismatches: u64,
+}
+
+impl OpStats {
+    /// Create empty stats whose samples keep `precision` significant decimal
+    /// digits
+    fn new(precision: u8) -> Self {
+        Self {
+            bandwidth: SampleSet::new(precision),
+            latency: SampleSet::new(precision),
+            counts: vec![],
+            entries: SampleSet::new(precision),
+            estimated_pages: SampleSet::new(precision),
+            part_latency: SampleSet::new(precision),
+            ttfb: SampleSet::new(precision),
+            verified: 0,
+            mismatches: 0,
+        }
+    }
+
+    /// Record an operation that took `latency`, completed in the `interval`-th
+    /// interval since the job started
+    fn add(&mut self, output: &Output, latency: Duration, interval: usize) {
+        self.latency.add(latency.as_micros() as f64);
+        self.bandwidth
+            .add(output.bytes as f64 / latency.as_secs_f64());
+        let counts = counts_at(&mut self.counts, interval);
+        counts.ops += 1;
+        counts.bytes += output.bytes;
+        counts.deleted += output.deleted.unwrap_or(0) as u64;
+        if let Some((entries, pages)) = output.listed {
+            self.entries.add(entries as f64 / latency.as_secs_f64());
+            self.estimated_pages
+                .add(pages as f64 / latency.as_secs_f64());
+        }
+        for part in &output.parts {
+            self.part_latency.add(part.as_micros() as f64);
+        }
+        if let Some(ttfb) = output.ttfb {
+            self.ttfb.add(ttfb.as_micros() as f64);
+        }
+        match output.verified {
+            Some(true) => self.verified += 1,
+            Some(false) => self.mismatches += 1,
+            None => {}
+        }
+    }
+
+    /// Merge two stats
+    pub fn merge(self, other: OpStats) -> Self {
+        Self {
+            bandwidth: self.bandwidth.merge(other.bandwidth),
+            latency: self.latency.merge(other.latency),
+            counts: merge_counts(self.counts, other.counts),
+            entries: self.entries.merge(other.entries),
+            estimated_pages: self.estimated_pages.merge(other.estimated_pages),
+            part_latency: self.part_latency.merge(other.part_latency),
+            ttfb: self.ttfb.merge(other.ttfb),
+            verified: self.verified + other.verified,
+            mismatches: self.mismatches + other.mismatches,
+        }
+    }
+}
+
+/// Samples collected while running a job
+#[derive(Debug, Clone)]
+pub struct JobStats {
+    /// Number of significant decimal digits kept by samples
+    pub precision: u8,
+    /// Samples of all operations
+    pub total: OpStats,
+    /// Samples of each kind of operation
+    pub operations: BTreeMap<Operation, OpStats>,
+    /// Samples of operations transferring whole objects, by size bucket of
+    /// the objects, see `size_bucket`
+    pub sizes: BTreeMap<u64, OpStats>,
+    /// Wall time taken by running operations
+    pub elapsed: Duration,
+    /// Number of writes issued by mixed workload in place of deletes, since
+    /// there was no object to delete yet
+    pub delete_fallbacks: u64,
+    /// Length of each interval of `OpStats::counts` and `latency_per_interval`
+    pub interval: Duration,
+    /// Latency in microseconds of operations completed by all workers in
+    /// each interval, `None` if no operation completed
+    pub latency_per_interval: Vec<Option<Metric>>,
+    /// Percentiles computed by metrics of the samples
+    pub percentiles: Vec<f64>,
+}
+
+impl JobStats {
+    /// Create empty stats whose samples keep `precision` significant decimal
+    /// digits
+    pub fn new(precision: u8) -> Self {
+        Self {
+            precision,
+            total: OpStats::new(precision),
+            operations: BTreeMap::new(),
+            sizes: BTreeMap::new(),
+            elapsed: Duration::ZERO,
+            delete_fallbacks: 0,
+            interval: Duration::ZERO,
+            latency_per_interval: vec![],
+            percentiles: vec![],
+        }
+    }
+
+    /// Merge two stats
+    pub fn merge(mut self, other: JobStats) -> Self {
+        self.total = self.total.merge(other.total);
+        for (op, stats) in other.operations {
+            let merged = match self.operations.remove(&op) {
+                Some(merged) => merged.merge(stats),
+                None => stats,
+            };
+            self.operations.insert(op, merged);
+        }
+        for (bucket, stats) in other.sizes {
+            let merged = match self.sizes.remove(&bucket) {
+                Some(merged) => merged.merge(stats),
+                None => stats,
+            };
+            self.sizes.insert(bucket, merged);
+        }
+        self.elapsed = self.elapsed.max(other.elapsed);
+        self.delete_fallbacks += other.delete_fallbacks;
+        self
+    }
+
+    /// Number of failed operations, only counted if `continue_on_error` is
+    /// enabled
+    pub fn errors(&self) -> u64 {
+        self.total.counts.iter().map(|counts| counts.errors).sum()
+    }
+}
+
+/// Counts of the `index`-th interval, growing `counts` as needed
+fn counts_at(counts: &mut Vec<Counts>, index: usize) -> &mut Counts {
+    if counts.len() <= index {
+        counts.resize(index + 1, Counts::default());
+    }
+    &mut counts[index]
+}
+
+/// Add counts of the same intervals
+fn merge_counts(mut counts: Vec<Counts>, other: Vec<Counts>) -> Vec<Counts> {
+    if counts.len() < other.len() {
+        counts.resize(other.len(), Counts::default());
+    }
+    for (counts, other) in counts.iter_mut().zip(other) {
+        *counts = counts.merge(other);
+    }
+    counts
+}
+
+/// Index of the interval of `interval` long that `elapsed` since the job
+/// started is in
+pub fn interval_index(elapsed: Duration, interval: Duration) -> usize {
+    (elapsed.as_nanos() / interval.as_nanos()) as usize
+}
+
+/// Size bucket of objects, which is the smallest power of two greater or
+/// equal to `size`, i.e. bucket `n` holds objects in (n / 2, n]
+fn size_bucket(size: u64) -> u64 {
+    size.next_power_of_two()
 }
 
 impl Job {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        let seed = config.job.seed.unwrap_or_else(rand::random);
+        let payload = PayloadBuffer::new(
+            config.job.payload.unwrap_or_default(),
+            config.job.compression_ratio.unwrap_or(2.0),
+            seed,
+        );
+
+        Self {
+            config,
+            seed,
+            payload,
+        }
     }
 
-    /// Run job, return sample set of (Bandwidth, Latency, IOPS)
-    pub fn run(&mut self) -> Result<(SampleSet, SampleSet, SampleSet), JobError> {
+    /// Seed of the generator of written data, which is random unless
+    /// configured
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Run job, return samples of all operations
+    pub fn run(&mut self) -> Result<JobStats, JobError> {
         let error = || JobError("failed to run job".to_string());
         let num_jobs = self.config.job.num_jobs.unwrap_or(1);
-        let start = std::time::Instant::now();
-        let run_time = self.config.job.run_time;
         let operator = build_operator(&self.config.service)?;
+        let cleanup_mode = self.config.job.cleanup.unwrap_or_default();
+        let written = WrittenKeys::new(cleanup_mode != Cleanup::Never);
 
         let runtime = tokio::runtime::Builder::new_multi_thread()
             .worker_threads(num_jobs as usize)
@@ -35,117 +318,1373 @@ impl Job {
             .build()
             .change_context_lazy(error)?;
 
-        let task = runtime.block_on(async { self.prepare_task().await })?;
+        // `None` if the job is interrupted by Ctrl-C
+        let result = runtime.block_on(async {
+            tokio::select! {
+                result = self.run_workers(&operator, &written) => Some(result),
+                _ = tokio::signal::ctrl_c() => None,
+            }
+        });
 
-        let mut handles: Vec<JoinHandle<Result<_, JobError>>> = vec![];
+        // An interrupted run is cleaned up as well unless cleanup is disabled
+        let need_cleanup = match cleanup_mode {
+            Cleanup::Always => true,
+            Cleanup::OnSuccess => !matches!(result, Some(Err(_))),
+            Cleanup::Never => false,
+        };
+        // Stats of the run are still returned if cleanup fails or is
+        // interrupted by Ctrl-C, leftover objects can be deleted by the
+        // cleanup command
+        if need_cleanup {
+            let cleaned = runtime.block_on(async {
+                tokio::select! {
+                    result = cleanup(&operator, written.take()) => Some(result),
+                    _ = tokio::signal::ctrl_c() => None,
+                }
+            });
+            match cleaned {
+                Some(Ok(())) => {}
+                Some(Err(e)) => eprintln!("{:#}", e),
+                None => eprintln!("cleanup interrupted, some written objects are left"),
+            }
+        }
 
-        for _ in 0..num_jobs {
+        result.unwrap_or_else(|| Err(Report::new(JobError("job interrupted".to_string()))))
+    }
+
+    /// Prepare task and run it in `num_jobs` workers until `run_time` elapsed
+    /// after `ramp_time`, whose operations are not measured
+    async fn run_workers(
+        &self,
+        operator: &Operator,
+        written: &WrittenKeys,
+    ) -> Result<JobStats, JobError> {
+        let error = || JobError("failed to run job".to_string());
+        let num_jobs = self.config.job.num_jobs.unwrap_or(1);
+        let run_time = self.config.job.run_time;
+        let ramp_time = self.config.job.ramp_time.unwrap_or_default();
+        let precision = self
+            .config
+            .job
+            .histogram_precision
+            .unwrap_or(DEFAULT_PRECISION);
+        let interval = self.config.job.interval.unwrap_or(Duration::from_secs(1));
+        let continue_on_error = self.config.job.continue_on_error.unwrap_or(false);
+        let percentiles = self
+            .config
+            .job
+            .percentiles
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PERCENTILES.to_vec());
+
+        let mut task = self.prepare_task(operator, written).await?;
+        // Start of the measured window
+        let start = std::time::Instant::now() + ramp_time;
+        let end = start + run_time;
+        let series = TimeSeries::new(interval, num_jobs as usize, precision, percentiles.clone());
+
+        // Workers are aborted once the set is dropped, e.g. on Ctrl-C
+        let mut workers: JoinSet<Result<_, JobError>> = JoinSet::new();
+
+        if let Some(refill) = task.take_refill() {
+            let operator = operator.clone();
+            let written = written.clone();
+            workers.spawn(async move {
+                refill.run(&operator, &written, end).await?;
+                Ok(JobStats::new(precision))
+            });
+        }
+
+        for worker in 0..num_jobs as usize {
             let operator = operator.clone();
-            let task = task.clone();
-            handles.push(runtime.spawn(async move {
-                let mut bandwidth = SampleSet::default();
-                let mut latency = SampleSet::default();
-                let mut iops = SampleSet::default();
-                let mut count = 0;
+            let written = written.clone();
+            let mut task = task.clone();
+            let mut recorder = series.recorder(worker);
+            workers.spawn(async move {
+                let mut stats = JobStats::new(precision);
                 loop {
-                    if start.elapsed() > run_time {
-                        return Ok((bandwidth, latency, iops));
+                    if std::time::Instant::now() > end {
+                        // Hand over the last interval before the stats
+                        drop(recorder);
+                        return Ok(stats);
+                    }
+                    if !task.prepare().await {
+                        drop(recorder);
+                        return Ok(stats);
                     }
                     let task_start = std::time::Instant::now();
-                    let bytes = task.run(&operator).await?;
-                    let lat = task_start.elapsed();
-                    count += 1;
-                    latency.add(lat.as_micros() as f64);
-                    bandwidth.add(bytes as f64 / lat.as_secs_f64());
-                    iops.add(count as f64 / start.elapsed().as_secs_f64());
+                    // Operations issued while ramping up are discarded
+                    let measured = task_start >= start;
+                    let output = match task.run(&operator, &written).await {
+                        Ok(_) if !measured => continue,
+                        Ok(output) => output,
+                        Err(e) if continue_on_error => {
+                            eprintln!("operation failed: {:#}", e);
+                            if measured {
+                                let index = interval_index(start.elapsed(), interval);
+                                counts_at(&mut stats.total.counts, index).errors += 1;
+                            }
+                            continue;
+                        }
+                        Err(e) => return Err(e),
+                    };
+                    let lat = task_start.elapsed() - output.unmeasured;
+                    let index = interval_index(start.elapsed(), interval);
+                    recorder.add(index, lat);
+
+                    stats.delete_fallbacks += output.delete_fallback as u64;
+                    stats.total.add(&output, lat, index);
+                    stats
+                        .operations
+                        .entry(output.op)
+                        .or_insert_with(|| OpStats::new(precision))
+                        .add(&output, lat, index);
+                    if let Some(object_size) = output.object_size {
+                        stats
+                            .sizes
+                            .entry(size_bucket(object_size))
+                            .or_insert_with(|| OpStats::new(precision))
+                            .add(&output, lat, index);
+                    }
                 }
-            }));
+            });
         }
 
-        let mut bandwidth = SampleSet::default();
-        let mut latency = SampleSet::default();
-        let mut iops = SampleSet::default();
-        for handle in handles {
-            let (bw, lat, iops_) =
-                runtime.block_on(async { handle.await.change_context_lazy(error) })??;
-            bandwidth = bandwidth.merge(bw);
-            latency = latency.merge(lat);
-            iops = iops.merge(iops_);
+        let mut stats = JobStats::new(precision);
+        while let Some(worker_stats) = workers.join_next().await {
+            stats = stats.merge(worker_stats.change_context_lazy(error)??);
         }
+        stats.elapsed = start.elapsed();
+        stats.interval = interval;
+        stats.latency_per_interval = series.finish(stats.elapsed);
+        stats.percentiles = percentiles;
 
-        Ok((bandwidth, latency, iops))
+        Ok(stats)
     }
 
-    async fn prepare_task(&self) -> Result<Task, JobError> {
-        let error = || JobError("failed to prepare task".to_string());
-
-        let path = format!("oio-test-{}", uuid::Uuid::new_v4());
-
+    async fn prepare_task(
+        &self,
+        operator: &Operator,
+        written: &WrittenKeys,
+    ) -> Result<Task, JobError> {
         match self.config.job.workload {
             Workload::Download => {
-                let buff = Bytes::from(vec![254u8; 4096]);
-                let operator = build_operator(&self.config.service)?;
+                let keys = self.prefill(operator, written).await?;
+
+                Ok(Task::Download {
+                    keys,
+                    options: self.read_options(),
+                })
+            }
+            Workload::Upload => Ok(Task::Upload {
+                keys: UploadKeyGenerator::new(
+                    self.config.job.upload_keys.unwrap_or_default(),
+                    self.config.job.num_upload_keys.unwrap_or(16),
+                ),
+                options: self.write_options()?,
+            }),
+            Workload::RangeRead => {
+                let keys = self.prefill(operator, written).await?;
+                let block_size = self
+                    .config
+                    .job
+                    .block_size
+                    .map_or(4096, Size::bytes)
+                    .min(self.config.job.file_size.min());
+
+                Ok(Task::RangeRead {
+                    verifier: self.verifier(),
+                    block_size,
+                    mode: self.config.job.range_mode.unwrap_or_default(),
+                    stride: self.config.job.stride.map_or(block_size * 2, Size::bytes),
+                    offsets: Arc::new(keys.paths.iter().map(|_| AtomicU64::new(0)).collect()),
+                    keys,
+                })
+            }
+            Workload::Mixed => {
+                let keys = self.prefill(operator, written).await?;
+                let mix = self.config.job.mix.clone().unwrap_or_default();
+
+                Ok(Task::Mixed {
+                    keys,
+                    read_options: self.read_options(),
+                    write_options: self.write_options()?,
+                    weights: mix_weights(&mix)?,
+                    deletable: vec![],
+                })
+            }
+            Workload::Stat if self.config.job.stat_missing.unwrap_or(false) => {
+                Ok(Task::StatMissing)
+            }
+            Workload::Stat => {
+                let keys = self.prefill(operator, written).await?;
+
+                Ok(Task::Stat { keys })
+            }
+            Workload::Delete | Workload::BatchDelete => {
+                let keys = self.prefill(operator, written).await?;
+                let batch_size = if self.config.job.workload == Workload::BatchDelete {
+                    self.config.job.delete_batch_size.unwrap_or(100)
+                } else {
+                    1
+                };
+
+                // Deletes make room for objects written in the background,
+                // so that targets never run out
+                let (sender, receiver) = mpsc::channel(keys.paths.len());
+                for path in keys.paths.iter() {
+                    sender
+                        .try_send(path.clone())
+                        .expect("channel has room for all prefilled objects");
+                }
+
+                Ok(Task::Delete {
+                    targets: Arc::new(tokio::sync::Mutex::new(receiver)),
+                    batch_size,
+                    refill: Some(Refill {
+                        sender,
+                        file_size: SizeSampler::new(&self.config.job.file_size)?,
+                        payload: self.prefill_payload(),
+                    }),
+                    exhausted: Arc::new(AtomicBool::new(false)),
+                    next: vec![],
+                })
+            }
+            Workload::List => Ok(Task::List {
+                root: self.prefill_directory(operator, written).await?,
+                recursive: self.config.job.list_recursive.unwrap_or(true),
+                page_size: self.config.job.list_page_size.unwrap_or(1000),
+            }),
+        }
+    }
+
+    fn read_options(&self) -> ReadOptions {
+        ReadOptions {
+            read_size: self.config.job.read_size.map_or(64 << 10, Size::bytes) as usize,
+            chunk_size: match (
+                self.config.job.read_chunk_size,
+                self.config.job.read_concurrency,
+            ) {
+                (Some(chunk_size), _) => Some(chunk_size.bytes() as usize),
+                (None, Some(_)) => Some(8 << 20),
+                (None, None) => None,
+            },
+            concurrency: self.config.job.read_concurrency.unwrap_or(1) as usize,
+            verifier: self.verifier(),
+        }
+    }
 
+    fn write_options(&self) -> Result<WriteOptions, JobError> {
+        let part_size = self.config.job.part_size.map(Size::bytes);
+        let write_size = match part_size {
+            Some(part_size) => part_size,
+            None => self.config.job.write_size.map_or(4096, Size::bytes),
+        };
+
+        Ok(WriteOptions {
+            file_size: SizeSampler::new(&self.config.job.file_size)?,
+            payload: self.payload(write_size),
+            part_size,
+            part_concurrency: self.config.job.part_concurrency,
+        })
+    }
+
+    /// Data written by writes of `write_size`
+    fn payload(&self, write_size: u64) -> PayloadBuffer {
+        self.payload.with_write_size(write_size as usize)
+    }
+
+    /// Data written by prefills
+    fn prefill_payload(&self) -> PayloadBuffer {
+        self.payload(self.config.job.write_size.map_or(4096, Size::bytes))
+    }
+
+    /// Verifier of reads if `verify` is enabled
+    fn verifier(&self) -> Option<Verifier> {
+        self.config.job.verify.unwrap_or(false).then(|| Verifier {
+            payload: self.payload.clone(),
+            method: self.config.job.verify_method.unwrap_or_default(),
+        })
+    }
+
+    /// Write `num_objects` objects of sizes sampled from `file_size` in
+    /// parallel, returns the selector of written objects
+    async fn prefill(
+        &self,
+        operator: &Operator,
+        written: &WrittenKeys,
+    ) -> Result<KeySelector, JobError> {
+        let num_objects = self.config.job.num_objects.unwrap_or(1);
+        let file_size = SizeSampler::new(&self.config.job.file_size)?;
+        let paths: Vec<String> = (0..num_objects).map(|_| new_object_key()).collect();
+        let sizes: Vec<u64> = paths.iter().map(|_| file_size.sample()).collect();
+        for path in &paths {
+            written.insert(path);
+        }
+        write_objects(
+            operator,
+            paths.iter().cloned().zip(sizes.iter().copied()).collect(),
+            self.prefill_payload(),
+        )
+        .await?;
+
+        KeySelector::new(
+            paths,
+            sizes,
+            self.config.job.key_distribution.unwrap_or_default(),
+            self.config.job.zipf_skew.unwrap_or(1.0),
+        )
+    }
+
+    /// Write `num_objects` objects of sampled sizes under a new directory, which
+    /// are spread evenly over `list_fanout ^ list_depth` nested directories.
+    /// Returns path of the directory.
+    async fn prefill_directory(
+        &self,
+        operator: &Operator,
+        written: &WrittenKeys,
+    ) -> Result<String, JobError> {
+        let num_objects = self.config.job.num_objects.unwrap_or(1);
+        let fanout = self.config.job.list_fanout.unwrap_or(10);
+        let depth = self.config.job.list_depth.unwrap_or(0);
+        let num_leaves = fanout.checked_pow(depth).ok_or_else(|| {
+            Report::new(JobError(format!(
+                "too many directories: {} ^ {}",
+                fanout, depth
+            )))
+        })?;
+
+        let file_size = SizeSampler::new(&self.config.job.file_size)?;
+
+        let root = format!("{}/", new_object_key());
+        written.insert(&root);
+
+        let objects = (0..num_objects)
+            .map(|i| {
+                let leaf = i % num_leaves;
+                let mut path = root.clone();
+                for level in (0..depth).rev() {
+                    path.push_str(&format!("dir-{}/", leaf / fanout.pow(level) % fanout));
+                }
+                path.push_str(&new_object_key());
+                (path, file_size.sample())
+            })
+            .collect();
+        write_objects(operator, objects, self.prefill_payload()).await?;
+
+        Ok(root)
+    }
+}
+
+/// Write objects of (path, size) in parallel
+async fn write_objects(
+    operator: &Operator,
+    objects: Vec<(String, u64)>,
+    payload: PayloadBuffer,
+) -> Result<(), JobError> {
+    let error = || JobError("failed to prefill object".to_string());
+
+    futures::stream::iter(objects)
+        .map(|(path, size)| {
+            let operator = operator.clone();
+            let payload = payload.clone();
+            async move {
                 let mut writer = operator.writer(&path).await.change_context_lazy(error)?;
-                for _ in 0..self.config.job.file_size / 4096 {
-                    writer
-                        .write(buff.clone())
-                        .await
-                        .change_context_lazy(error)?;
+                for chunk in payload.chunks(&path, size) {
+                    writer.write(chunk).await.change_context_lazy(error)?;
                 }
                 writer.close().await.change_context_lazy(error)?;
+                Ok::<_, Report<JobError>>(())
+            }
+        })
+        .buffer_unordered(PREFILL_CONCURRENCY)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    Ok(())
+}
 
-                Ok(Task::Download { path })
+/// Data written by uploads and prefills, which is generated once and sliced
+/// by each write without copying
+#[derive(Clone, Debug)]
+struct PayloadBuffer {
+    /// Data of `PAYLOAD_POOL_SIZE` bytes followed by a copy of its first
+    /// `MAX_SLICE_LEN` bytes, so that a slice can start at any offset of the
+    /// pool regardless of the write size
+    pool: Bytes,
+    write_size: usize,
+}
+
+impl PayloadBuffer {
+    /// Generate payload from `seed`, which is written by writes of 4096 bytes
+    /// unless changed by `with_write_size`
+    fn new(payload: Payload, compression_ratio: f64, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut pool = vec![0u8; PAYLOAD_POOL_SIZE];
+        match payload {
+            Payload::Zeros => {}
+            Payload::Random => rng.fill(&mut pool[..]),
+            Payload::Compressible => {
+                // Each block is random data followed by zeros, which
+                // compresses to about the random part
+                let random_len = (COMPRESSIBLE_BLOCK_SIZE as f64 / compression_ratio).ceil();
+                for block in pool.chunks_mut(COMPRESSIBLE_BLOCK_SIZE) {
+                    let len = (random_len as usize).min(block.len());
+                    rng.fill(&mut block[..len]);
+                }
+            }
+        }
+        pool.extend_from_within(..MAX_SLICE_LEN);
+
+        Self {
+            pool: Bytes::from(pool),
+            write_size: 4096,
+        }
+    }
+
+    /// Payload sharing the same data, which is written by writes of
+    /// `write_size`
+    fn with_write_size(&self, write_size: usize) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            write_size,
+        }
+    }
+
+    /// Chunks making up an object of `size` bytes at `path`, all of which are
+    /// of `write_size` except the last one. Each chunk is made of slices of
+    /// the pool, so no data is copied.
+    fn chunks<'a>(&'a self, path: &'a str, size: u64) -> impl Iterator<Item = Buffer> + 'a {
+        (0..size).step_by(self.write_size).map(move |offset| {
+            let end = (offset + self.write_size as u64).min(size);
+            self.slices(path, offset..end, MAX_SLICE_LEN).collect()
+        })
+    }
+
+    /// Slices of at most `max_len` bytes making up `range` of the object at
+    /// `path`. Each `PAYLOAD_POOL_SIZE` segment of an object is the pool
+    /// repeated from an offset derived from its path and the index of the
+    /// segment, so that data is neither repeated within an object nor shared
+    /// by objects at the same offsets, and can be regenerated to verify reads.
+    fn slices<'a>(
+        &'a self,
+        path: &'a str,
+        range: Range<u64>,
+        max_len: usize,
+    ) -> impl Iterator<Item = Bytes> + 'a {
+        let pool_size = PAYLOAD_POOL_SIZE as u64;
+        let mut offset = range.start;
+        std::iter::from_fn(move || {
+            if offset >= range.end {
+                return None;
+            }
+            // Slices do not cross segments, which start at different offsets
+            // of the pool
+            let segment = offset / pool_size;
+            let segment_end = (segment + 1) * pool_size;
+            let len = (range.end.min(segment_end) - offset).min(max_len as u64) as usize;
+            // CRC32C is stable across builds, unlike the hasher of std
+            let segment_offset =
+                crc32c::crc32c_append(crc32c::crc32c(path.as_bytes()), &segment.to_le_bytes())
+                    as u64;
+            let start = ((segment_offset + offset) % pool_size) as usize;
+            offset += len as u64;
+            Some(self.pool.slice(start..start + len))
+        })
+    }
+}
+
+/// Checks data of reads against the data written to objects
+#[derive(Clone, Debug)]
+struct Verifier {
+    payload: PayloadBuffer,
+    method: VerifyMethod,
+}
+
+impl Verifier {
+    /// Start checking data read from `range` of the object at `path`
+    fn check<'a>(&'a self, path: &'a str, range: Range<u64>) -> Check<'a> {
+        let state = match self.method {
+            VerifyMethod::Bytes => CheckState::Bytes { matched: true },
+            VerifyMethod::Crc32c => CheckState::Crc32c {
+                actual: 0,
+                expected: 0,
+            },
+            VerifyMethod::Md5 => CheckState::Md5 {
+                actual: md5::Context::new(),
+                expected: md5::Context::new(),
+            },
+        };
+
+        Check {
+            verifier: self,
+            path,
+            offset: range.start,
+            range,
+            state,
+        }
+    }
+
+    /// Whether `data` read from `offset` of the object at `path` matches the
+    /// written data
+    #[cfg(test)]
+    fn verify(&self, path: &str, offset: u64, data: &[u8]) -> bool {
+        let mut check = self.check(path, offset..offset + data.len() as u64);
+        check.update(data);
+        check.finish()
+    }
+}
+
+/// Checks data read from an object chunk by chunk against the written data,
+/// so that reads do not need to keep the whole data
+struct Check<'a> {
+    verifier: &'a Verifier,
+    path: &'a str,
+    /// Range expected to be read
+    range: Range<u64>,
+    /// Offset of next chunk
+    offset: u64,
+    state: CheckState,
+}
+
+enum CheckState {
+    Bytes {
+        matched: bool,
+    },
+    Crc32c {
+        actual: u32,
+        expected: u32,
+    },
+    Md5 {
+        actual: md5::Context,
+        expected: md5::Context,
+    },
+}
+
+impl Check<'_> {
+    /// Check next chunk of read data
+    fn update(&mut self, data: &[u8]) {
+        let range = self.offset..self.offset + data.len() as u64;
+        self.offset = range.end;
+        let payload = &self.verifier.payload;
+        let mut slices = payload.slices(self.path, range, MAX_SLICE_LEN);
+        match &mut self.state {
+            CheckState::Bytes { matched } => {
+                let mut pos = 0;
+                *matched = *matched
+                    && slices.all(|slice| {
+                        pos += slice.len();
+                        data[pos - slice.len()..pos] == slice[..]
+                    });
+            }
+            CheckState::Crc32c { actual, expected } => {
+                *actual = crc32c::crc32c_append(*actual, data);
+                *expected = slices.fold(*expected, |crc, slice| crc32c::crc32c_append(crc, &slice));
+            }
+            CheckState::Md5 { actual, expected } => {
+                actual.consume(data);
+                slices.for_each(|slice| expected.consume(&slice));
+            }
+        }
+    }
+
+    /// Whether all data of the range was read and matched, mismatches are
+    /// printed to stderr
+    fn finish(self) -> bool {
+        let matched = self.offset == self.range.end
+            && match self.state {
+                CheckState::Bytes { matched } => matched,
+                CheckState::Crc32c { actual, expected } => actual == expected,
+                CheckState::Md5 { actual, expected } => actual.compute() == expected.compute(),
+            };
+        if !matched {
+            eprintln!(
+                "data mismatch: {} in range {:?}, read {} bytes",
+                self.path,
+                self.range,
+                self.offset - self.range.start
+            );
+        }
+        matched
+    }
+}
+
+/// Size of the pool of written data, which is larger than windows of common
+/// compression algorithms. Each segment of this size of an object starts at
+/// a different offset of the pool.
+const PAYLOAD_POOL_SIZE: usize = 8 << 20;
+
+/// Length of the longest slice of the pool, which is followed by a copy of its
+/// beginning so that a slice can start at any offset. Longer writes are made
+/// of multiple slices.
+const MAX_SLICE_LEN: usize = 1 << 20;
+
+/// Size of blocks of compressible payload, each of which is compressible by
+/// the compression ratio
+const COMPRESSIBLE_BLOCK_SIZE: usize = 4096;
+
+/// Number of objects written concurrently while prefilling
+const PREFILL_CONCURRENCY: usize = 32;
+
+/// Keys of objects written during a run, which are deleted on cleanup.
+/// Keys ending with `/` are directories, which are deleted recursively.
+/// Nothing is tracked if cleanup is disabled, so that memory does not grow
+/// with the number of written objects.
+#[derive(Clone, Debug, Default)]
+struct WrittenKeys(Option<Arc<Mutex<BTreeSet<String>>>>);
+
+impl WrittenKeys {
+    /// Track written keys if `enabled`
+    fn new(enabled: bool) -> Self {
+        Self(enabled.then(Default::default))
+    }
+
+    /// Track a key, which should be called before the object is written so
+    /// that interrupted writes get cleaned up as well
+    fn insert(&self, key: &str) {
+        if let Some(keys) = &self.0 {
+            let mut keys = keys.lock().unwrap();
+            if !keys.contains(key) {
+                keys.insert(key.to_string());
+            }
+        }
+    }
+
+    /// Stop tracking a deleted key
+    fn remove(&self, key: &str) {
+        if let Some(keys) = &self.0 {
+            keys.lock().unwrap().remove(key);
+        }
+    }
+
+    /// Take all tracked keys
+    fn take(&self) -> Vec<String> {
+        match &self.0 {
+            Some(keys) => std::mem::take(&mut *keys.lock().unwrap())
+                .into_iter()
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
+/// Delete objects written during a run, in batches if the service supports
+async fn cleanup(operator: &Operator, keys: Vec<String>) -> Result<(), JobError> {
+    let num_keys = keys.len();
+    let error = || JobError(format!("failed to clean up {} objects", num_keys));
+
+    let (dirs, files): (Vec<String>, Vec<String>) =
+        keys.into_iter().partition(|key| key.ends_with('/'));
+    operator.remove(files).await.change_context_lazy(error)?;
+    futures::future::try_join_all(dirs.iter().map(|dir| operator.remove_all(dir)))
+        .await
+        .change_context_lazy(error)?;
+
+    Ok(())
+}
+
+/// Chooses the object each operation targets from a prefilled dataset
+#[derive(Clone, Debug)]
+struct KeySelector {
+    paths: Arc<Vec<String>>,
+    /// Size of each object
+    sizes: Arc<Vec<u64>>,
+    distribution: KeyDistribution,
+    zipf: Zipf<f64>,
+    /// Index of next object of sequential distribution, shared by all workers
+    next: Arc<AtomicUsize>,
+}
+
+impl KeySelector {
+    fn new(
+        paths: Vec<String>,
+        sizes: Vec<u64>,
+        distribution: KeyDistribution,
+        skew: f64,
+    ) -> Result<Self, JobError> {
+        let zipf = Zipf::new(paths.len() as u64, skew)
+            .change_context_lazy(|| JobError("invalid zipfian key distribution".to_string()))?;
+
+        Ok(Self {
+            paths: Arc::new(paths),
+            sizes: Arc::new(sizes),
+            distribution,
+            zipf,
+            next: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    /// Path of the object targeted by next operation
+    fn next(&self) -> &str {
+        &self.paths[self.next_index()]
+    }
+
+    /// Path and size of the object targeted by next operation
+    fn next_with_size(&self) -> (&str, u64) {
+        self.get(self.next_index())
+    }
+
+    /// Path and size of the `index`-th object
+    fn get(&self, index: usize) -> (&str, u64) {
+        (&self.paths[index], self.sizes[index])
+    }
+
+    fn next_index(&self) -> usize {
+        match self.distribution {
+            KeyDistribution::Uniform => rand::thread_rng().gen_range(0..self.paths.len()),
+            // Zipf samples ranks in [1, n], rank 1 being the hottest
+            KeyDistribution::Zipfian => self.zipf.sample(&mut rand::thread_rng()) as usize - 1,
+            KeyDistribution::Sequential => {
+                self.next.fetch_add(1, Ordering::Relaxed) % self.paths.len()
+            }
+        }
+    }
+}
+
+/// Samples size of each written object from `file_size`
+#[derive(Clone, Debug)]
+enum SizeSampler {
+    Fixed(u64),
+    Uniform(Uniform<u64>),
+    Buckets {
+        sizes: Vec<u64>,
+        weights: WeightedIndex<u32>,
+    },
+    LogNormal(LogNormal<f64>),
+}
+
+impl SizeSampler {
+    fn new(file_size: &FileSize) -> Result<Self, JobError> {
+        let error = || JobError(format!("invalid file_size distribution: {}", file_size));
+
+        let sampler = match file_size {
+            FileSize::Fixed(size) => SizeSampler::Fixed(size.bytes()),
+            FileSize::Distribution(SizeDistribution::Uniform { min, max }) => {
+                SizeSampler::Uniform(Uniform::new_inclusive(min.bytes(), max.bytes()))
+            }
+            FileSize::Distribution(SizeDistribution::Buckets { buckets }) => SizeSampler::Buckets {
+                sizes: buckets.iter().map(|bucket| bucket.size.bytes()).collect(),
+                weights: WeightedIndex::new(buckets.iter().map(|bucket| bucket.weight))
+                    .change_context_lazy(error)?,
+            },
+            FileSize::Distribution(SizeDistribution::LogNormal { mean, stddev }) => {
+                // Parameters of the underlying normal distribution giving the
+                // mean and standard deviation of sizes
+                let mean = mean.bytes() as f64;
+                let stddev = stddev.bytes() as f64;
+                let sigma = (1.0 + (stddev / mean).powi(2)).ln().sqrt();
+                let mu = mean.ln() - sigma.powi(2) / 2.0;
+                SizeSampler::LogNormal(LogNormal::new(mu, sigma).change_context_lazy(error)?)
+            }
+        };
+
+        Ok(sampler)
+    }
+
+    /// Size of next written object
+    fn sample(&self) -> u64 {
+        match self {
+            SizeSampler::Fixed(size) => *size,
+            SizeSampler::Uniform(uniform) => uniform.sample(&mut rand::thread_rng()),
+            SizeSampler::Buckets { sizes, weights } => {
+                sizes[weights.sample(&mut rand::thread_rng())]
+            }
+            SizeSampler::LogNormal(log_normal) => {
+                (log_normal.sample(&mut rand::thread_rng()).round() as u64).max(MIN_FILE_SIZE)
+            }
+        }
+    }
+}
+
+/// Writes objects in the background to replace the ones deleted by workers,
+/// which is not measured
+#[derive(Clone, Debug)]
+struct Refill {
+    sender: mpsc::Sender<String>,
+    file_size: SizeSampler,
+    payload: PayloadBuffer,
+}
+
+impl Refill {
+    /// Write objects whenever deletes make room for a batch, until `end`
+    async fn run(
+        self,
+        operator: &Operator,
+        written: &WrittenKeys,
+        end: std::time::Instant,
+    ) -> Result<(), JobError> {
+        let batch_size = PREFILL_CONCURRENCY.min(self.sender.max_capacity());
+        loop {
+            let permits = tokio::select! {
+                permits = self.sender.reserve_many(batch_size) => match permits {
+                    Ok(permits) => permits,
+                    // All workers stopped
+                    Err(_) => return Ok(()),
+                },
+                _ = tokio::time::sleep_until(end.into()) => return Ok(()),
+            };
+            let objects: Vec<(String, u64)> = (0..batch_size)
+                .map(|_| (new_object_key(), self.file_size.sample()))
+                .collect();
+            for (path, _) in &objects {
+                written.insert(path);
+            }
+            let paths: Vec<String> = objects.iter().map(|(path, _)| path.clone()).collect();
+            write_objects(operator, objects, self.payload.clone()).await?;
+            for (permit, path) in permits.zip(paths) {
+                permit.send(path);
             }
-            Workload::Upload => Ok(Task::Upload {
-                path,
-                file_size: self.config.job.file_size,
-            }),
         }
     }
 }
 
+/// Generates the keys written by uploads
+#[derive(Clone, Debug)]
+struct UploadKeyGenerator {
+    strategy: UploadKeys,
+    /// Key shared by all workers
+    fixed: String,
+    /// Key prefix of this worker, generated on first use so that workers
+    /// cloned from the same task never share rotating keys
+    worker_prefix: Option<String>,
+    num_keys: u32,
+    /// Index of next rotating key
+    next: u32,
+}
+
+impl UploadKeyGenerator {
+    fn new(strategy: UploadKeys, num_keys: u32) -> Self {
+        Self {
+            strategy,
+            fixed: new_object_key(),
+            worker_prefix: None,
+            num_keys,
+            next: 0,
+        }
+    }
+
+    /// Key written by next upload
+    fn next(&mut self) -> String {
+        match self.strategy {
+            UploadKeys::Unique => new_object_key(),
+            UploadKeys::Rotating => {
+                let prefix = self.worker_prefix.get_or_insert_with(new_object_key);
+                let key = format!("{}-{}", prefix, self.next);
+                self.next = (self.next + 1) % self.num_keys;
+                key
+            }
+            UploadKeys::Fixed => self.fixed.clone(),
+        }
+    }
+}
+
+/// Options of downloads
+#[derive(Clone, Debug)]
+struct ReadOptions {
+    /// Size of the buffer objects are streamed through
+    read_size: usize,
+    /// Size of each range fetched separately by chunked downloads, which
+    /// stream the whole object at once if not set
+    chunk_size: Option<usize>,
+    /// Number of ranges of a chunked download fetched concurrently
+    concurrency: usize,
+    /// Verifier of downloaded data if `verify` is enabled
+    verifier: Option<Verifier>,
+}
+
+/// Options of uploads
+#[derive(Clone, Debug)]
+struct WriteOptions {
+    file_size: SizeSampler,
+    payload: PayloadBuffer,
+    /// Size of each part of multipart uploads, uses default of the writer if
+    /// not set
+    part_size: Option<u64>,
+    /// Number of parts uploaded concurrently
+    part_concurrency: Option<u32>,
+}
+
 #[derive(Clone, Debug)]
 enum Task {
-    Download { path: String },
-    Upload { path: String, file_size: u32 },
+    Download {
+        keys: KeySelector,
+        options: ReadOptions,
+    },
+    Upload {
+        keys: UploadKeyGenerator,
+        options: WriteOptions,
+    },
+    RangeRead {
+        keys: KeySelector,
+        verifier: Option<Verifier>,
+        block_size: u64,
+        mode: RangeMode,
+        stride: u64,
+        /// Offset of next sequential or strided read of each object, shared
+        /// by all workers
+        offsets: Arc<Vec<AtomicU64>>,
+    },
+    Mixed {
+        /// Prefilled objects to read and stat
+        keys: KeySelector,
+        read_options: ReadOptions,
+        write_options: WriteOptions,
+        /// Weights of (read, write, stat, delete)
+        weights: WeightedIndex<u32>,
+        /// Objects written by this worker, which are targets of deletes
+        deletable: Vec<String>,
+    },
+    Stat {
+        keys: KeySelector,
+    },
+    /// Stat keys that never exist
+    StatMissing,
+    /// Delete `batch_size` objects at once, with a single delete if
+    /// `batch_size` is 1
+    Delete {
+        /// Objects not deleted yet, which are prefilled and then refilled in
+        /// the background, shared by all workers
+        targets: Arc<tokio::sync::Mutex<mpsc::Receiver<String>>>,
+        batch_size: u32,
+        /// Writer of new targets, taken by the job before workers start
+        refill: Option<Refill>,
+        /// Whether workers have run out of prefilled targets
+        exhausted: Arc<AtomicBool>,
+        /// Objects deleted by next operation
+        next: Vec<String>,
+    },
+    /// Enumerate all objects under `root`
+    List {
+        root: String,
+        /// List recursively with one lister, or walk through directories
+        /// level by level otherwise
+        recursive: bool,
+        page_size: u32,
+    },
+}
+
+/// Outcome of a single operation
+struct Output {
+    op: Operation,
+    /// Processed bytes
+    bytes: u64,
+    /// Number of (entries, estimated pages) listed
+    listed: Option<(u32, u32)>,
+    /// Number of objects deleted
+    deleted: Option<u32>,
+    /// Latencies of each part of a multipart upload
+    parts: Vec<Duration>,
+    /// Time to first byte of a download
+    ttfb: Option<Duration>,
+    /// Size of the object if it is transferred as a whole
+    object_size: Option<u64>,
+    /// Whether the read data matches the written data, if verified
+    verified: Option<bool>,
+    /// Time taken by verifying data, which is excluded from latency
+    unmeasured: Duration,
+    /// Whether it is a write issued in place of a delete
+    delete_fallback: bool,
+}
+
+impl Output {
+    fn new(op: Operation, bytes: u64) -> Self {
+        Self {
+            op,
+            bytes,
+            listed: None,
+            deleted: None,
+            parts: vec![],
+            ttfb: None,
+            object_size: None,
+            verified: None,
+            unmeasured: Duration::ZERO,
+            delete_fallback: false,
+        }
+    }
+}
+
+const MIXED_OPERATIONS: [Operation; 4] = [
+    Operation::Read,
+    Operation::Write,
+    Operation::Stat,
+    Operation::Delete,
+];
+
+fn mix_weights(mix: &Mix) -> Result<WeightedIndex<u32>, JobError> {
+    WeightedIndex::new([mix.read, mix.write, mix.stat, mix.delete])
+        .change_context_lazy(|| JobError("invalid operation weights".to_string()))
 }
 
 impl Task {
-    /// Run task with operator, returns processed bytes
-    pub async fn run(&self, operator: &Operator) -> Result<u32, JobError> {
+    /// Prepare next operation without being measured, returns false if
+    /// there is no operation left
+    pub async fn prepare(&mut self) -> bool {
+        if let Task::Delete {
+            targets,
+            batch_size,
+            exhausted,
+            next,
+            ..
+        } = self
+        {
+            let mut targets = targets.lock().await;
+            if targets.is_empty() && !exhausted.swap(true, Ordering::Relaxed) {
+                eprintln!(
+                    "no objects left to delete, waiting for objects written in the \
+                     background, increase num_objects to measure deletes only"
+                );
+            }
+            // Targets run out once refill stops at the end of the run, the
+            // last batch may be smaller
+            while next.len() < *batch_size as usize {
+                let limit = *batch_size as usize - next.len();
+                if targets.recv_many(next, limit).await == 0 {
+                    break;
+                }
+            }
+            return !next.is_empty();
+        }
+
+        true
+    }
+
+    /// Take the writer of new targets run beside workers, if any
+    pub fn take_refill(&mut self) -> Option<Refill> {
+        match self {
+            Task::Delete { refill, .. } => refill.take(),
+            _ => None,
+        }
+    }
+
+    /// Run task with operator, returns the performed operation
+    pub async fn run(
+        &mut self,
+        operator: &Operator,
+        written: &WrittenKeys,
+    ) -> Result<Output, JobError> {
         match self {
-            Task::Download { path } => {
-                let res = operator.read_with(path).await.change_context_lazy(|| {
-                    JobError(format!("failed to download object: {}", path))
-                })?;
-                Ok(res.len() as u32)
-            }
-            Task::Upload { path, file_size } => {
-                let buff = Bytes::from(vec![254u8; *file_size as usize]);
-
-                let mut writer = operator.writer(path).await.change_context_lazy(|| {
-                    JobError(format!("failed to upload object: {}", path))
-                })?;
-
-                for _ in 0..*file_size / 4096 {
-                    writer.write(buff.clone()).await.change_context_lazy(|| {
-                        JobError(format!("failed to upload object: {}", path))
+            Task::Download { keys, options } => {
+                download(operator, keys.next_with_size(), options).await
+            }
+            Task::Upload { keys, options } => {
+                let path = keys.next();
+                written.insert(&path);
+                upload(operator, &path, options).await
+            }
+            Task::RangeRead {
+                keys,
+                verifier,
+                block_size,
+                mode,
+                stride,
+                offsets,
+            } => {
+                let index = keys.next_index();
+                let (path, object_size) = keys.get(index);
+                let start = match mode {
+                    RangeMode::Random => {
+                        rand::thread_rng().gen_range(0..=object_size - *block_size)
+                    }
+                    RangeMode::Sequential | RangeMode::Strided => {
+                        let step = if *mode == RangeMode::Strided {
+                            *stride
+                        } else {
+                            *block_size
+                        };
+                        // Wrap around once the block exceeds the object
+                        let wrap = |offset: u64| {
+                            if offset + *block_size > object_size {
+                                0
+                            } else {
+                                offset
+                            }
+                        };
+                        let offset = offsets[index]
+                            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |offset| {
+                                Some(wrap(offset) + step)
+                            })
+                            .expect("offset is always updated");
+                        wrap(offset)
+                    }
+                };
+                let range = start..start + *block_size;
+
+                let res = operator
+                    .read_with(path)
+                    .range(range.clone())
+                    .await
+                    .change_context_lazy(|| {
+                        JobError(format!("failed to read range of object: {}", path))
                     })?;
+                let mut output = Output::new(Operation::Read, res.len() as u64);
+                if let Some(verifier) = verifier {
+                    let check_start = std::time::Instant::now();
+                    let mut check = verifier.check(path, range);
+                    for chunk in res {
+                        check.update(&chunk);
+                    }
+                    output.verified = Some(check.finish());
+                    output.unmeasured = check_start.elapsed();
                 }
+                Ok(output)
+            }
+            Task::Stat { keys } => {
+                stat(operator, keys.next()).await?;
+                Ok(Output::new(Operation::Stat, 0))
+            }
+            Task::StatMissing => {
+                let path = new_object_key();
+                match operator.stat(&path).await {
+                    Err(e) if e.kind() == ErrorKind::NotFound => {
+                        Ok(Output::new(Operation::Stat, 0))
+                    }
+                    Err(e) => Err(Report::new(e).change_context(JobError(format!(
+                        "failed to stat missing object: {}",
+                        path
+                    )))),
+                    Ok(_) => Err(Report::new(JobError(format!(
+                        "missing object exists: {}",
+                        path
+                    )))),
+                }
+            }
+            Task::Delete {
+                batch_size, next, ..
+            } => {
+                let paths = std::mem::take(next);
+                let num_deleted = paths.len() as u32;
+                let error = || JobError(format!("failed to delete {} objects", num_deleted));
+                if *batch_size == 1 {
+                    operator
+                        .delete(&paths[0])
+                        .await
+                        .change_context_lazy(error)?;
+                } else {
+                    operator
+                        .remove(paths.clone())
+                        .await
+                        .change_context_lazy(error)?;
+                }
+                for path in &paths {
+                    written.remove(path);
+                }
+                Ok(Output {
+                    deleted: Some(num_deleted),
+                    ..Output::new(Operation::Delete, 0)
+                })
+            }
+            Task::List {
+                root,
+                recursive,
+                page_size,
+            } => {
+                let listed = list(operator, root, *recursive, *page_size).await?;
+                Ok(Output {
+                    listed: Some(listed),
+                    ..Output::new(Operation::List, 0)
+                })
+            }
+            Task::Mixed {
+                keys,
+                read_options,
+                write_options,
+                weights,
+                deletable,
+            } => {
+                let op = MIXED_OPERATIONS[weights.sample(&mut rand::thread_rng())];
+                match op {
+                    Operation::Read => {
+                        download(operator, keys.next_with_size(), read_options).await
+                    }
+                    Operation::Stat => {
+                        stat(operator, keys.next()).await?;
+                        Ok(Output::new(op, 0))
+                    }
+                    // Deletes remove objects written before, and fall back to
+                    // a write if there is nothing to delete yet
+                    Operation::Delete if !deletable.is_empty() => {
+                        let target =
+                            deletable.swap_remove(rand::thread_rng().gen_range(0..deletable.len()));
+                        operator.delete(&target).await.change_context_lazy(|| {
+                            JobError(format!("failed to delete object: {}", target))
+                        })?;
+                        written.remove(&target);
+                        Ok(Output::new(op, 0))
+                    }
+                    Operation::Write | Operation::Delete => {
+                        let target = new_object_key();
+                        written.insert(&target);
+                        let output = upload(operator, &target, write_options).await?;
+                        deletable.push(target);
+                        Ok(Output {
+                            delete_fallback: op == Operation::Delete,
+                            ..output
+                        })
+                    }
+                    Operation::List => unreachable!("list is not a mixed operation"),
+                }
+            }
+        }
+    }
+}
+
+/// Read the whole object of `size` at `path` by streaming it through a buffer
+/// of `read_size`, which keeps memory bounded regardless of object size.
+/// Chunked downloads fetch ranges of `chunk_size` with up to `concurrency`
+/// of them in flight, and still hand them over in order. Measures the time to
+/// first byte, and verifies data chunk by chunk if `verify` is enabled.
+async fn download(
+    operator: &Operator,
+    (path, size): (&str, u64),
+    options: &ReadOptions,
+) -> Result<Output, JobError> {
+    let error = || JobError(format!("failed to download object: {}", path));
+    let start = std::time::Instant::now();
 
-                writer.close().await.change_context_lazy(|| {
-                    JobError(format!("failed to upload object: {}", path))
-                })?;
+    // Reading the known size avoids the stat before reading a whole object
+    let mut reader = operator.reader_with(path);
+    if let Some(chunk_size) = options.chunk_size {
+        reader = reader.chunk(chunk_size).concurrent(options.concurrency);
+    }
+    let mut reader = reader
+        .await
+        .change_context_lazy(error)?
+        .into_futures_async_read(0..size)
+        .await
+        .change_context_lazy(error)?;
 
-                Ok(*file_size / 4096 * 4096)
+    let mut buf = vec![0u8; options.read_size];
+    let mut check = options
+        .verifier
+        .as_ref()
+        .map(|verifier| verifier.check(path, 0..size));
+    let mut output = Output::new(Operation::Read, 0);
+    loop {
+        let n = reader.read(&mut buf).await.change_context_lazy(error)?;
+        if n == 0 {
+            break;
+        }
+        output.ttfb.get_or_insert_with(|| start.elapsed());
+        output.bytes += n as u64;
+
+        if let Some(check) = &mut check {
+            let check_start = std::time::Instant::now();
+            check.update(&buf[..n]);
+            output.unmeasured += check_start.elapsed();
+        }
+    }
+    output.object_size = Some(output.bytes);
+    output.verified = check.map(Check::finish);
+
+    Ok(output)
+}
+
+/// Enumerate all entries under `root`, returns number of (entries, pages).
+/// Pages are not counted from actual requests, which are hidden by the lister,
+/// but estimated as the requests needed to list each directory with
+/// `page_size` entries per page, which is only a hint to the service.
+async fn list(
+    operator: &Operator,
+    root: &str,
+    recursive: bool,
+    page_size: u32,
+) -> Result<(u32, u32), JobError> {
+    let error = || JobError(format!("failed to list objects: {}", root));
+
+    let mut dirs = vec![root.to_string()];
+    let mut num_entries = 0;
+    let mut num_pages = 0;
+    while let Some(dir) = dirs.pop() {
+        let mut lister = operator
+            .lister_with(&dir)
+            .recursive(recursive)
+            .limit(page_size as usize)
+            .await
+            .change_context_lazy(error)?;
+
+        let mut dir_entries: u32 = 0;
+        while let Some(entry) = lister.try_next().await.change_context_lazy(error)? {
+            // Some services return the listed directory itself
+            if entry.path() == dir {
+                continue;
             }
+            if !recursive && entry.metadata().is_dir() {
+                dirs.push(entry.path().to_string());
+            }
+            dir_entries += 1;
         }
+        num_entries += dir_entries;
+        num_pages += dir_entries.div_ceil(page_size).max(1);
     }
+
+    Ok((num_entries, num_pages))
+}
+
+/// Stat an existing object
+async fn stat(operator: &Operator, path: &str) -> Result<(), JobError> {
+    operator
+        .stat(path)
+        .await
+        .change_context_lazy(|| JobError(format!("failed to stat object: {}", path)))?;
+    Ok(())
+}
+
+/// Write an object of size sampled from `file_size`
+async fn upload(
+    operator: &Operator,
+    path: &str,
+    options: &WriteOptions,
+) -> Result<Output, JobError> {
+    let error = || JobError(format!("failed to upload object: {}", path));
+    let file_size = options.file_size.sample();
+
+    let mut writer = operator.writer_with(path);
+    if let Some(part_size) = options.part_size {
+        writer = writer.chunk(part_size as usize);
+    }
+    if let Some(part_concurrency) = options.part_concurrency {
+        writer = writer.concurrent(part_concurrency as usize);
+    }
+    let mut writer = writer.await.change_context_lazy(error)?;
+
+    // With multipart options, writes are of part size so that data is
+    // written part by part to time each part, which is the time taken by the
+    // writer to accept the part. It includes uploading a part if parts are
+    // uploaded one by one, or waiting for a free slot to upload the part
+    // concurrently otherwise.
+    let mut parts = vec![];
+    for chunk in options.payload.chunks(path, file_size) {
+        let part_start = std::time::Instant::now();
+        writer.write(chunk).await.change_context_lazy(error)?;
+        if options.part_size.is_some() {
+            parts.push(part_start.elapsed());
+        }
+    }
+
+    // Closing the writer uploads the last part and completes the upload
+    let close_start = std::time::Instant::now();
+    writer.close().await.change_context_lazy(error)?;
+    if options.part_size.is_some() {
+        parts.push(close_start.elapsed());
+    }
+
+    Ok(Output {
+        parts,
+        object_size: Some(file_size),
+        ..Output::new(Operation::Write, file_size)
+    })
+}
+
+/// Prefix of keys of all objects written by oio
+pub const TEST_OBJECT_PREFIX: &str = "oio-test-";
+
+/// Generate a new unique key of test object
+fn new_object_key() -> String {
+    format!("{}{}", TEST_OBJECT_PREFIX, uuid::Uuid::new_v4())
 }
 
 /// Build OpenDAL operator from config
-fn build_operator(service: &Service) -> Result<Operator, JobError> {
+pub fn build_operator(service: &Service) -> Result<Operator, JobError> {
     let operator = match service.type_ {
         ServiceType::S3 | ServiceType::Minio => {
             let mut builder = opendal::services::S3::default();
@@ -210,3 +1749,480 @@ fn build_operator(service: &Service) -> Result<Operator, JobError> {
 
     Ok(operator)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Config of job on a `fs` service rooted at `dir`, which keeps written
+    /// objects unless cleanup is configured
+    fn fs_config(dir: &std::path::Path, job: &str) -> Config {
+        let config = format!(
+            r#"
+            [service]
+            type = "fs"
+            endpoint = ""
+            bucket = ""
+            access_key = ""
+            secret_key = ""
+            prefix = "{}"
+
+            [job]
+            run_time = "200ms"
+            {}
+            "#,
+            dir.display(),
+            job
+        );
+        let mut config: Config = toml::from_str(&config).unwrap();
+        config.validate().unwrap();
+        config.job.cleanup.get_or_insert(Cleanup::Never);
+        config
+    }
+
+    /// Run job on a `fs` service rooted at `dir`
+    fn run_fs_job(dir: &std::path::Path, job: &str) -> JobStats {
+        Job::new(fs_config(dir, job)).run().unwrap()
+    }
+
+    /// Sizes of all files under `dir`
+    fn file_sizes(dir: &std::path::Path) -> Vec<u64> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().metadata().unwrap().len())
+            .collect()
+    }
+
+    #[test]
+    fn test_upload_object_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = run_fs_job(
+            dir.path(),
+            r#"
+            workload = "upload"
+            file_size = 10000
+            write_size = 3000
+            "#,
+        );
+
+        let sizes = file_sizes(dir.path());
+        assert!(!sizes.is_empty());
+        assert!(sizes.iter().all(|size| *size == 10000), "{:?}", sizes);
+        assert_eq!(stats.total.latency.num_samples(), sizes.len());
+        assert_eq!(
+            stats
+                .total
+                .counts
+                .iter()
+                .map(|counts| counts.bytes)
+                .sum::<u64>(),
+            10000 * sizes.len() as u64
+        );
+    }
+
+    #[test]
+    fn test_upload_keys() {
+        for (upload_keys, num_files) in [
+            // Each worker rotates through keys of its own
+            (r#"upload_keys = "rotating""#, 6),
+            // All workers overwrite the same key
+            (r#"upload_keys = "fixed""#, 1),
+        ] {
+            let dir = tempfile::tempdir().unwrap();
+            let stats = run_fs_job(
+                dir.path(),
+                &format!(
+                    r#"
+                    workload = "upload"
+                    file_size = 4096
+                    num_jobs = 2
+                    num_upload_keys = 3
+                    {}
+                    "#,
+                    upload_keys
+                ),
+            );
+
+            assert!(stats.total.latency.num_samples() > num_files);
+            assert_eq!(file_sizes(dir.path()).len(), num_files);
+        }
+    }
+
+    #[test]
+    fn test_ramp_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = run_fs_job(
+            dir.path(),
+            r#"
+            workload = "upload"
+            file_size = 4096
+            ramp_time = "100ms"
+            "#,
+        );
+
+        // Objects uploaded while ramping up are not measured
+        let num_objects = file_sizes(dir.path()).len();
+        assert!(stats.total.latency.num_samples() > 0);
+        assert!(stats.total.latency.num_samples() < num_objects);
+        assert!(
+            stats.elapsed < Duration::from_millis(300),
+            "{:?}",
+            stats.elapsed
+        );
+    }
+
+    #[test]
+    fn test_multipart_upload_object_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = run_fs_job(
+            dir.path(),
+            r#"
+            workload = "upload"
+            file_size = "1MiB"
+            part_size = "300KiB"
+            "#,
+        );
+
+        let sizes = file_sizes(dir.path());
+        assert!(!sizes.is_empty());
+        assert!(sizes.iter().all(|size| *size == 1 << 20), "{:?}", sizes);
+        // 4 writes of parts, the last of which is partial, and closing
+        assert_eq!(stats.total.part_latency.num_samples(), sizes.len() * 5,);
+    }
+
+    #[test]
+    fn test_prefill_object_size() {
+        let dir = tempfile::tempdir().unwrap();
+        run_fs_job(
+            dir.path(),
+            r#"
+            workload = "download"
+            file_size = 10000
+            num_objects = 4
+            "#,
+        );
+
+        assert_eq!(file_sizes(dir.path()), vec![10000; 4]);
+    }
+
+    #[test]
+    fn test_delete_refilled_objects() {
+        for workload in [
+            r#"workload = "delete""#,
+            r#"workload = "batch_delete"
+            delete_batch_size = 7"#,
+        ] {
+            let dir = tempfile::tempdir().unwrap();
+            let stats = run_fs_job(
+                dir.path(),
+                &format!(
+                    r#"
+                    {}
+                    file_size = 4096
+                    num_objects = 50
+                    num_jobs = 2
+                    "#,
+                    workload
+                ),
+            );
+
+            // Deleted objects are replaced in the background, which never
+            // leaves more objects than prefilled
+            assert!(file_sizes(dir.path()).len() <= 50);
+            assert!(
+                stats
+                    .total
+                    .counts
+                    .iter()
+                    .map(|counts| counts.deleted)
+                    .sum::<u64>()
+                    > 50
+            );
+        }
+    }
+
+    #[test]
+    fn test_cleanup_written_objects() {
+        for workload in [
+            // Uploads write files, and list prefills a directory tree
+            r#"workload = "upload""#,
+            r#"workload = "list"
+            num_objects = 10
+            list_depth = 2
+            list_fanout = 2"#,
+        ] {
+            let dir = tempfile::tempdir().unwrap();
+            run_fs_job(
+                dir.path(),
+                &format!(
+                    r#"
+                    {}
+                    file_size = 4096
+                    num_jobs = 2
+                    cleanup = "always"
+                    "#,
+                    workload
+                ),
+            );
+
+            assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_list_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let job = Job::new(fs_config(
+            dir.path(),
+            r#"
+            workload = "list"
+            file_size = 4096
+            num_objects = 10
+            list_depth = 2
+            list_fanout = 3
+            "#,
+        ));
+        let operator = build_operator(&job.config.service).unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let root = job
+                .prefill_directory(&operator, &WrittenKeys::default())
+                .await
+                .unwrap();
+
+            // 3 + 9 directories and 10 objects, each directory is listed by
+            // its own pages if walked
+            assert_eq!(list(&operator, &root, true, 2).await.unwrap(), (22, 11));
+            assert_eq!(list(&operator, &root, false, 2).await.unwrap(), (22, 17));
+        });
+    }
+
+    #[test]
+    fn test_key_distributions() {
+        let selector = |distribution| {
+            let paths = (0..4).map(|i| i.to_string()).collect();
+            KeySelector::new(paths, vec![4096; 4], distribution, 1.0).unwrap()
+        };
+        let counts = |keys: &KeySelector| {
+            let mut counts = [0; 4];
+            for _ in 0..10000 {
+                counts[keys.next_index()] += 1;
+            }
+            counts
+        };
+
+        let uniform = counts(&selector(KeyDistribution::Uniform));
+        assert!(uniform.iter().all(|&count| count > 2000), "{:?}", uniform);
+
+        // Rank 1 of Zipf is the first key, and the last key is still chosen
+        let zipfian = counts(&selector(KeyDistribution::Zipfian));
+        assert!(zipfian.windows(2).all(|w| w[0] > w[1]), "{:?}", zipfian);
+        assert!(zipfian[3] > 0);
+
+        // Clones of workers share the cursor of sequential keys
+        let keys = selector(KeyDistribution::Sequential);
+        let other = keys.clone();
+        let indices: Vec<usize> = (0..6)
+            .map(|i| if i % 2 == 0 { &keys } else { &other }.next_index())
+            .collect();
+        assert_eq!(indices, vec![0, 1, 2, 3, 0, 1]);
+    }
+
+    #[test]
+    fn test_mixed_operations() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = run_fs_job(
+            dir.path(),
+            r#"
+            workload = "mixed"
+            file_size = 4096
+            num_objects = 4
+            num_jobs = 2
+
+            [job.mix]
+            read = 1
+            write = 1
+            stat = 1
+            delete = 1
+            "#,
+        );
+
+        let ops: Vec<Operation> = stats.operations.keys().copied().collect();
+        assert_eq!(
+            ops,
+            vec![
+                Operation::Read,
+                Operation::Write,
+                Operation::Stat,
+                Operation::Delete
+            ]
+        );
+
+        // Reads and stats of prefilled objects would fail once deleted, and
+        // every delete removes one of the written objects
+        let num_samples = |op| stats.operations[&op].latency.num_samples();
+        assert!(stats.delete_fallbacks as usize <= num_samples(Operation::Write));
+        assert_eq!(
+            file_sizes(dir.path()).len(),
+            4 + num_samples(Operation::Write) - num_samples(Operation::Delete)
+        );
+    }
+
+    #[test]
+    fn test_stat_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = run_fs_job(
+            dir.path(),
+            r#"
+            workload = "stat"
+            file_size = 4096
+            num_objects = 4
+            stat_missing = true
+            "#,
+        );
+
+        // Missing keys are stat instead of prefilled objects
+        assert!(file_sizes(dir.path()).is_empty());
+        assert!(stats.total.latency.num_samples() > 0);
+        assert_eq!(
+            stats.operations[&Operation::Stat].latency.num_samples(),
+            stats.total.latency.num_samples()
+        );
+    }
+
+    #[test]
+    fn test_time_series() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = run_fs_job(
+            dir.path(),
+            r#"
+            workload = "stat"
+            file_size = 4096
+            num_jobs = 4
+            interval = "50ms"
+            "#,
+        );
+
+        // At least 4 full intervals and the partial last one
+        let num_intervals = stats.latency_per_interval.len();
+        assert!(num_intervals >= 5, "{:?}", stats.latency_per_interval);
+        assert!(stats.total.counts.len() <= num_intervals);
+        assert_eq!(
+            stats
+                .total
+                .counts
+                .iter()
+                .map(|counts| counts.ops)
+                .sum::<u64>(),
+            stats.total.latency.num_samples() as u64
+        );
+    }
+
+    #[test]
+    fn test_verify_reads() {
+        let dir = tempfile::tempdir().unwrap();
+        for (workload, download) in [
+            (r#"workload = "download""#, true),
+            (
+                r#"workload = "range_read"
+                block_size = 3000
+                range_mode = "random""#,
+                false,
+            ),
+            (
+                r#"workload = "download"
+                read_chunk_size = 3000
+                read_concurrency = 3"#,
+                true,
+            ),
+        ] {
+            let stats = run_fs_job(
+                dir.path(),
+                &format!(
+                    r#"
+                    {}
+                    file_size = 10000
+                    num_objects = 4
+                    verify = true
+                    verify_method = "md5"
+                    "#,
+                    workload
+                ),
+            );
+            assert!(stats.total.verified > 0);
+            assert_eq!(stats.total.mismatches, 0);
+
+            // Time to first byte is only sampled by downloads, and is part of
+            // their latency
+            let (ttfb, latency) = (&stats.total.ttfb, &stats.total.latency);
+            if download {
+                assert_eq!(ttfb.num_samples(), latency.num_samples());
+                assert!(ttfb.max() <= latency.max());
+            } else {
+                assert_eq!(ttfb.num_samples(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_payload_segments() {
+        let payload = PayloadBuffer::new(Payload::Random, 2.0, 0).with_write_size(3 << 20);
+        let size = 2 * PAYLOAD_POOL_SIZE as u64 + 1000;
+        let data: Vec<u8> = payload
+            .chunks("object", size)
+            .flat_map(|chunk| chunk.to_vec())
+            .collect();
+        assert_eq!(data.len() as u64, size);
+
+        // Each segment starts at a different offset of the pool
+        let segment = |i: usize| &data[i * PAYLOAD_POOL_SIZE..][..1000];
+        assert_ne!(segment(0), segment(1));
+        assert_ne!(segment(1), segment(2));
+
+        let verifier = Verifier {
+            payload,
+            method: VerifyMethod::Bytes,
+        };
+        assert!(verifier.verify("object", 0, &data));
+        let start = PAYLOAD_POOL_SIZE - 500;
+        assert!(verifier.verify("object", start as u64, &data[start..][..1000]));
+    }
+
+    #[test]
+    fn test_verify_corrupted_data() {
+        let payload = PayloadBuffer::new(Payload::Random, 2.0, 0);
+        let mut data: Vec<u8> = payload
+            .chunks("object", 100_000)
+            .flat_map(|chunk| chunk.to_vec())
+            .collect();
+        assert_eq!(data.len(), 100_000);
+
+        for method in [VerifyMethod::Bytes, VerifyMethod::Crc32c, VerifyMethod::Md5] {
+            let verifier = Verifier {
+                payload: payload.clone(),
+                method,
+            };
+            assert!(verifier.verify("object", 0, &data));
+            assert!(verifier.verify("object", 5000, &data[5000..60000]));
+            assert!(!verifier.verify("other", 0, &data));
+        }
+
+        // Data is regenerated by payload of the same seed only
+        for (seed, matched) in [(0, true), (1, false)] {
+            let verifier = Verifier {
+                payload: PayloadBuffer::new(Payload::Random, 2.0, seed),
+                method: VerifyMethod::Bytes,
+            };
+            assert_eq!(verifier.verify("object", 0, &data), matched);
+        }
+
+        data[50_000] ^= 1;
+        for method in [VerifyMethod::Bytes, VerifyMethod::Crc32c, VerifyMethod::Md5] {
+            let verifier = Verifier {
+                payload: payload.clone(),
+                method,
+            };
+            assert!(!verifier.verify("object", 0, &data));
+        }
+    }
+}
@@ -1,27 +1,56 @@
+mod cleanup;
 mod config;
 mod job;
 mod report;
 mod sample;
+mod series;
 
-use config::Config;
+use config::{Config, Service, ServiceConfig};
 use error_stack::{Result, ResultExt};
 use job::Job;
 use report::Report;
-use std::{fs::File, io::Read, process::exit};
+use std::{fs::File, io::Read, process::exit, time::Duration};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use thiserror::Error;
 
 #[derive(Debug, Parser)]
 #[command(version, about, long_about = None)]
 pub struct Args {
-    config_file: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the benchmark job described by config file
+    Run { config_file: String },
+    /// Delete test objects left under the configured prefix, e.g. by
+    /// crashed or interrupted runs
+    Cleanup {
+        config_file: String,
+        /// Only list the objects to delete
+        #[arg(long)]
+        dry_run: bool,
+        /// Only delete objects last modified earlier than this, e.g. "1h", "7days"
+        #[arg(long, value_parser = humantime::parse_duration)]
+        older_than: Option<Duration>,
+    },
 }
 
 fn main() {
     let args = Args::parse();
 
-    match run(&args) {
+    let result = match &args.command {
+        Command::Run { config_file } => run(config_file),
+        Command::Cleanup {
+            config_file,
+            dry_run,
+            older_than,
+        } => cleanup(config_file, *dry_run, *older_than),
+    };
+
+    match result {
         Ok(_) => exit(0),
         Err(e) => {
             eprintln!("{:?}", e);
@@ -34,30 +63,71 @@ fn main() {
 #[error("{0}")]
 struct CliError(pub String);
 
-fn run(args: &Args) -> Result<(), CliError> {
-    let error = || CliError("failed to run job".to_string());
+fn read_config_file(config_file: &str) -> Result<String, CliError> {
+    let error = || CliError(format!("failed to load config: {}", config_file));
 
-    let mut config_file = File::open(&args.config_file).change_context_lazy(error)?;
+    let mut config_file = File::open(config_file).change_context_lazy(error)?;
     let mut buf = vec![];
     config_file
         .read_to_end(&mut buf)
         .change_context_lazy(error)?;
-    let config_str = String::from_utf8(buf).change_context_lazy(error)?;
+    String::from_utf8(buf).change_context_lazy(error)
+}
+
+fn load_config(config_file: &str) -> Result<Config, CliError> {
+    let error = || CliError(format!("failed to load config: {}", config_file));
+
+    let config_str = read_config_file(config_file)?;
     let config: Config = toml::from_str(&config_str).change_context_lazy(error)?;
     config.validate().change_context_lazy(error)?;
 
+    Ok(config)
+}
+
+/// Load only the service of config file, the job is not needed to clean up
+fn load_service(config_file: &str) -> Result<Service, CliError> {
+    let error = || CliError(format!("failed to load config: {}", config_file));
+
+    let config_str = read_config_file(config_file)?;
+    let config: ServiceConfig = toml::from_str(&config_str).change_context_lazy(error)?;
+
+    Ok(config.service)
+}
+
+fn run(config_file: &str) -> Result<(), CliError> {
+    let error = || CliError("failed to run job".to_string());
+
+    let config = load_config(config_file).change_context_lazy(error)?;
+
     let mut job = Job::new(config.clone());
-    let (bandwidth, latency, iops) = job.run().change_context_lazy(error)?;
+    let stats = job.run().change_context_lazy(error)?;
 
     let report = Report::new(
         config.job.num_jobs.unwrap_or(1),
-        config.job.file_size,
+        config.job.file_size.clone(),
         config.job.workload.to_string(),
-        bandwidth,
-        latency,
-        iops,
+        job.seed(),
+        stats,
     );
     println!("{}", report);
 
     Ok(())
 }
+
+fn cleanup(config_file: &str, dry_run: bool, older_than: Option<Duration>) -> Result<(), CliError> {
+    let error = || CliError("failed to clean up".to_string());
+
+    let service = load_service(config_file).change_context_lazy(error)?;
+
+    let paths = cleanup::cleanup(&service, dry_run, older_than).change_context_lazy(error)?;
+    for path in &paths {
+        println!("{}", path);
+    }
+    if dry_run {
+        println!("{} objects would be deleted", paths.len());
+    } else {
+        println!("{} objects deleted", paths.len());
+    }
+
+    Ok(())
+}
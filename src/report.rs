@@ -1,184 +1,1013 @@
-use std::{fmt::Display, time::Duration};
+use std::{collections::BTreeMap, fmt::Display, time::Duration};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::sample::SampleSet;
+use crate::{
+    config::FileSize,
+    job::{Counts, JobStats, OpStats},
+    sample::SampleSet,
+};
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Percentiles computed by default, besides min and max
+pub const DEFAULT_PERCENTILES: [f64; 5] = [50.0, 90.0, 99.0, 99.9, 99.99];
+
+/// Percentile of samples, e.g. `Percentile(99.9)` is formatted as "p99.9"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Percentile(pub f64);
+
+impl Eq for Percentile {}
+
+impl PartialOrd for Percentile {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Percentile {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Display for Percentile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "p{}", self.0)
+    }
+}
+
+impl Serialize for Percentile {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Percentile {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.strip_prefix('p')
+            .and_then(|p| p.parse().ok())
+            .map(Percentile)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid percentile: {}", s)))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Metric {
     num_samples: u32,
     min: f64,
     max: f64,
     avg: f64,
     stdev: f64,
-    p99: f64,
-    p95: f64,
-    p50: f64,
+    /// values of each configured percentile, in ascending order
+    percentiles: BTreeMap<Percentile, f64>,
+}
+
+impl Metric {
+    pub fn new(samples: &SampleSet, percentiles: &[f64]) -> Self {
+        Self {
+            num_samples: samples.num_samples() as u32,
+            min: samples.min(),
+            max: samples.max(),
+            avg: samples.avg(),
+            stdev: samples.stdev(),
+            percentiles: percentiles
+                .iter()
+                .map(|p| (Percentile(*p), samples.percentile(*p)))
+                .collect(),
+        }
+    }
+
+    /// Build metric only if there is any sample
+    pub fn new_if_sampled(samples: &SampleSet, percentiles: &[f64]) -> Option<Self> {
+        (samples.num_samples() > 0).then(|| Self::new(samples, percentiles))
+    }
+}
+
+/// Results of verifying data of reads
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Verification {
+    /// Number of reads whose data matched the written data
+    verified: u64,
+    /// Number of reads whose data did not match the written data
+    mismatches: u64,
+}
+
+impl Verification {
+    /// Build verification results only if any read was verified
+    fn new_if_verified(stats: &OpStats) -> Option<Self> {
+        (stats.verified + stats.mismatches > 0).then_some(Self {
+            verified: stats.verified,
+            mismatches: stats.mismatches,
+        })
+    }
+}
+
+/// Throughput achieved by all workers together
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Throughput {
+    /// total bytes transferred
+    bytes: u64,
+    /// wall time of the job in seconds
+    elapsed: f64,
+    /// total bytes transferred divided by wall time in bytes/s
+    avg: f64,
+    /// bytes transferred per second in each full interval in bytes/s
+    per_interval: Metric,
+}
+
+impl Throughput {
+    fn new(stats: &JobStats) -> Self {
+        let bytes = stats.total.counts.iter().map(|counts| counts.bytes).sum();
+        let elapsed = stats.elapsed.as_secs_f64();
+
+        Self {
+            bytes,
+            elapsed,
+            avg: bytes as f64 / elapsed,
+            per_interval: rate_metric(&stats.total.counts, |counts| counts.bytes, stats),
+        }
+    }
+}
+
+/// Metrics of operations completed by all workers in an interval of the run
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IntervalMetrics {
+    /// start of the interval in seconds since the job started
+    start: f64,
+    /// throughput in bytes/s
+    throughput: f64,
+    /// completed operations per second
+    iops: f64,
+    /// number of failed operations
+    errors: u64,
+    /// latency in microseconds, absent if no operation completed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    latency: Option<Metric>,
+}
+
+impl IntervalMetrics {
+    /// Build metrics of each interval, the last of which is cut short by the
+    /// end of the job
+    fn new_series(stats: &JobStats) -> Vec<Self> {
+        let interval = stats.interval.as_secs_f64();
+        let elapsed = stats.elapsed.as_secs_f64();
+        stats
+            .latency_per_interval
+            .iter()
+            .enumerate()
+            .map(|(i, latency)| {
+                let start = i as f64 * interval;
+                let duration = interval.min(elapsed - start);
+                let counts = stats.total.counts.get(i).copied().unwrap_or_default();
+                Self {
+                    start,
+                    throughput: counts.bytes as f64 / duration,
+                    iops: counts.ops as f64 / duration,
+                    errors: counts.errors,
+                    latency: latency.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Metrics of one kind of operation
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpMetrics {
+    /// throughput in bytes/s
+    bandwidth: Metric,
+    /// latency in microseconds
+    latency: Metric,
+    /// operations completed by all workers per second in each interval
+    iops: Metric,
+    /// listed entries per second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entries: Option<Metric>,
+    /// estimated pages listed per second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    estimated_pages: Option<Metric>,
+    /// objects deleted by all workers per second in each interval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deleted: Option<Metric>,
+    /// latency of each part of multipart uploads in microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part_latency: Option<Metric>,
+    /// time to first byte of downloads in microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ttfb: Option<Metric>,
+    /// results of verifying data of reads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verification: Option<Verification>,
+}
+
+/// Build metric of `count` of `counts` per second in each full interval, or
+/// in the whole run if it is shorter than an interval
+fn rate_metric(counts: &[Counts], count: impl Fn(&Counts) -> u64, job: &JobStats) -> Metric {
+    let num_intervals = (job.elapsed.as_nanos() / job.interval.as_nanos()) as usize;
+    let mut rates = SampleSet::new(job.precision);
+    if num_intervals == 0 {
+        let total: u64 = counts.iter().map(&count).sum();
+        rates.add(total as f64 / job.elapsed.as_secs_f64());
+    }
+    for i in 0..num_intervals {
+        let n = counts.get(i).map_or(0, &count);
+        rates.add(n as f64 / job.interval.as_secs_f64());
+    }
+    Metric::new(&rates, &job.percentiles)
+}
+
+/// Build metric of deleted objects per second, if any object is deleted
+fn deleted_metric(stats: &OpStats, job: &JobStats) -> Option<Metric> {
+    stats
+        .counts
+        .iter()
+        .any(|counts| counts.deleted > 0)
+        .then(|| rate_metric(&stats.counts, |counts| counts.deleted, job))
+}
+
+impl OpMetrics {
+    fn new(stats: &OpStats, job: &JobStats) -> Self {
+        Self {
+            bandwidth: Metric::new(&stats.bandwidth, &job.percentiles),
+            latency: Metric::new(&stats.latency, &job.percentiles),
+            iops: rate_metric(&stats.counts, |counts| counts.ops, job),
+            entries: Metric::new_if_sampled(&stats.entries, &job.percentiles),
+            estimated_pages: Metric::new_if_sampled(&stats.estimated_pages, &job.percentiles),
+            deleted: deleted_metric(stats, job),
+            part_latency: Metric::new_if_sampled(&stats.part_latency, &job.percentiles),
+            ttfb: Metric::new_if_sampled(&stats.ttfb, &job.percentiles),
+            verification: Verification::new_if_verified(stats),
+        }
+    }
+}
+
+/// Metrics of operations transferring objects of sizes in
+/// (min_size, max_size]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SizeMetrics {
+    /// Object size in bytes, exclusive
+    min_size: u64,
+    /// Object size in bytes, inclusive
+    max_size: u64,
+    #[serde(flatten)]
+    metrics: OpMetrics,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Report {
     /// Number of parallel jobs
     num_jobs: u32,
-    /// File size in bytes
-    file_size: u32,
+    /// File size, either fixed or a distribution
+    file_size: FileSize,
     /// Workload
     workload: String,
+    /// seed of the generator of written data
+    seed: u64,
+    /// throughput of all workers together
+    throughput: Throughput,
+    /// number of failed operations, only counted if `continue_on_error` is
+    /// enabled
+    errors: u64,
+    /// number of writes issued by mixed workload in place of deletes, since
+    /// there was no object to delete yet
+    delete_fallbacks: u64,
     /// throughput in bytes/s
     bandwidth: Metric,
     /// latency in microseconds
     latency: Metric,
-    /// I/O operations per second
+    /// operations completed by all workers per second in each interval
     iops: Metric,
+    /// listed entries per second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entries: Option<Metric>,
+    /// estimated pages listed per second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    estimated_pages: Option<Metric>,
+    /// objects deleted by all workers per second in each interval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deleted: Option<Metric>,
+    /// latency of each part of multipart uploads in microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    part_latency: Option<Metric>,
+    /// time to first byte of downloads in microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ttfb: Option<Metric>,
+    /// results of verifying data of reads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verification: Option<Verification>,
+    /// Metrics of each kind of operation, only present if more than one kind
+    /// of operation was issued
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    operations: BTreeMap<String, OpMetrics>,
+    /// Metrics of whole object reads and writes by object size, only present
+    /// if objects of more than one size bucket were transferred
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sizes: Vec<SizeMetrics>,
+    /// length of each interval of the time series in seconds
+    interval: f64,
+    /// metrics of each interval of the run
+    series: Vec<IntervalMetrics>,
 }
 
 impl Report {
     pub fn new(
         num_jobs: u32,
-        file_size: u32,
+        file_size: FileSize,
         workload: String,
-        bandwidth: SampleSet,
-        latency: SampleSet,
-        iops: SampleSet,
+        seed: u64,
+        stats: JobStats,
     ) -> Self {
+        let operations = if stats.operations.len() > 1 {
+            stats
+                .operations
+                .iter()
+                .map(|(op, op_stats)| (op.to_string(), OpMetrics::new(op_stats, &stats)))
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+        let sizes = if stats.sizes.len() > 1 {
+            stats
+                .sizes
+                .iter()
+                .map(|(bucket, size_stats)| SizeMetrics {
+                    min_size: bucket / 2,
+                    max_size: *bucket,
+                    metrics: OpMetrics::new(size_stats, &stats),
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
         Self {
             num_jobs,
             file_size,
             workload,
-            bandwidth: Metric {
-                num_samples: bandwidth.num_samples() as u32,
-                min: bandwidth.min(),
-                max: bandwidth.max(),
-                avg: bandwidth.avg(),
-                stdev: bandwidth.stdev(),
-                p99: bandwidth.percentile(99.0),
-                p95: bandwidth.percentile(95.0),
-                p50: bandwidth.percentile(50.0),
-            },
-            latency: Metric {
-                num_samples: latency.num_samples() as u32,
-                min: latency.min(),
-                max: latency.max(),
-                avg: latency.avg(),
-                stdev: latency.stdev(),
-                p99: latency.percentile(99.0),
-                p95: latency.percentile(95.0),
-                p50: latency.percentile(50.0),
-            },
-            iops: Metric {
-                num_samples: iops.num_samples() as u32,
-                min: iops.min(),
-                max: iops.max(),
-                avg: iops.avg(),
-                stdev: iops.stdev(),
-                p99: iops.percentile(99.0),
-                p95: iops.percentile(95.0),
-                p50: iops.percentile(50.0),
-            },
+            seed,
+            throughput: Throughput::new(&stats),
+            errors: stats.errors(),
+            delete_fallbacks: stats.delete_fallbacks,
+            bandwidth: Metric::new(&stats.total.bandwidth, &stats.percentiles),
+            latency: Metric::new(&stats.total.latency, &stats.percentiles),
+            iops: rate_metric(&stats.total.counts, |counts| counts.ops, &stats),
+            entries: Metric::new_if_sampled(&stats.total.entries, &stats.percentiles),
+            estimated_pages: Metric::new_if_sampled(
+                &stats.total.estimated_pages,
+                &stats.percentiles,
+            ),
+            deleted: deleted_metric(&stats.total, &stats),
+            part_latency: Metric::new_if_sampled(&stats.total.part_latency, &stats.percentiles),
+            ttfb: Metric::new_if_sampled(&stats.total.ttfb, &stats.percentiles),
+            verification: Verification::new_if_verified(&stats.total),
+            operations,
+            sizes,
+            interval: stats.interval.as_secs_f64(),
+            series: IntervalMetrics::new_series(&stats),
         }
     }
 }
 
-impl Display for Report {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "Number of parallel jobs: {}", self.num_jobs)?;
+fn fmt_bandwidth(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    bandwidth: &Metric,
+) -> std::fmt::Result {
+    writeln!(f, "{}:", name)?;
+    writeln!(f, "  num_samples: {}", bandwidth.num_samples)?;
+    writeln!(
+        f,
+        "  min: {}/s",
+        humansize::format_size(bandwidth.min as u64, humansize::BINARY)
+    )?;
+    writeln!(
+        f,
+        "  max: {}/s",
+        humansize::format_size(bandwidth.max as u64, humansize::BINARY)
+    )?;
+    writeln!(
+        f,
+        "  avg: {}/s",
+        humansize::format_size(bandwidth.avg as u64, humansize::BINARY)
+    )?;
+    writeln!(
+        f,
+        "  stdev: {}/s",
+        humansize::format_size(bandwidth.stdev as u64, humansize::BINARY)
+    )?;
+    for (percentile, value) in &bandwidth.percentiles {
         writeln!(
             f,
-            "File size: {}",
-            humansize::format_size(self.file_size as u64, humansize::BINARY)
+            "  {}: {}/s",
+            percentile,
+            humansize::format_size(*value as u64, humansize::BINARY)
         )?;
-        writeln!(f, "Workload: {}", self.workload)?;
+    }
 
-        writeln!(f)?;
+    Ok(())
+}
 
-        writeln!(f, "Bandwidth:")?;
-        writeln!(f, "  num_samples: {}", self.bandwidth.num_samples)?;
-        writeln!(
-            f,
-            "  min: {}/s",
-            humansize::format_size(self.bandwidth.min as u64, humansize::BINARY)
-        )?;
-        writeln!(
-            f,
-            "  max: {}/s",
-            humansize::format_size(self.bandwidth.max as u64, humansize::BINARY)
-        )?;
-        writeln!(
-            f,
-            "  avg: {}/s",
-            humansize::format_size(self.bandwidth.avg as u64, humansize::BINARY)
-        )?;
-        writeln!(
-            f,
-            "  stdev: {}/s",
-            humansize::format_size(self.bandwidth.stdev as u64, humansize::BINARY)
-        )?;
-        writeln!(
-            f,
-            "  p99: {}/s",
-            humansize::format_size(self.bandwidth.p99 as u64, humansize::BINARY)
-        )?;
-        writeln!(
-            f,
-            "  p95: {}/s",
-            humansize::format_size(self.bandwidth.p95 as u64, humansize::BINARY)
-        )?;
+fn fmt_throughput(f: &mut std::fmt::Formatter<'_>, throughput: &Throughput) -> std::fmt::Result {
+    writeln!(f, "Throughput:")?;
+    writeln!(
+        f,
+        "  total: {} in {}",
+        humansize::format_size(throughput.bytes, humansize::BINARY),
+        humantime::format_duration(Duration::from_millis((throughput.elapsed * 1000.0) as u64))
+    )?;
+    writeln!(
+        f,
+        "  avg: {}/s",
+        humansize::format_size(throughput.avg as u64, humansize::BINARY)
+    )?;
+    writeln!(f)?;
+    fmt_bandwidth(f, "Throughput per interval", &throughput.per_interval)?;
+
+    Ok(())
+}
+
+fn fmt_latency(f: &mut std::fmt::Formatter<'_>, name: &str, latency: &Metric) -> std::fmt::Result {
+    writeln!(f, "{}:", name)?;
+    writeln!(f, "  num_samples: {}", latency.num_samples)?;
+    writeln!(
+        f,
+        "  min: {}",
+        humantime::format_duration(Duration::from_micros(latency.min as u64))
+    )?;
+    writeln!(
+        f,
+        "  max: {}",
+        humantime::format_duration(Duration::from_micros(latency.max as u64))
+    )?;
+    writeln!(
+        f,
+        "  avg: {}",
+        humantime::format_duration(Duration::from_micros(latency.avg as u64))
+    )?;
+    writeln!(
+        f,
+        "  stdev: {}",
+        humantime::format_duration(Duration::from_micros(latency.stdev as u64))
+    )?;
+    for (percentile, value) in &latency.percentiles {
         writeln!(
             f,
-            "  p50: {}/s",
-            humansize::format_size(self.bandwidth.p50 as u64, humansize::BINARY)
+            "  {}: {}",
+            percentile,
+            humantime::format_duration(Duration::from_micros(*value as u64))
         )?;
+    }
+
+    Ok(())
+}
+
+/// Format metric of a rate, e.g. IOPS
+fn fmt_rate(f: &mut std::fmt::Formatter<'_>, name: &str, rate: &Metric) -> std::fmt::Result {
+    writeln!(f, "{}:", name)?;
+    writeln!(f, "  num_samples: {:.3}", rate.num_samples)?;
+    writeln!(f, "  min: {:.3}", rate.min)?;
+    writeln!(f, "  max: {:.3}", rate.max)?;
+    writeln!(f, "  avg: {:.3}", rate.avg)?;
+    writeln!(f, "  stdev: {:.3}", rate.stdev)?;
+    for (percentile, value) in &rate.percentiles {
+        writeln!(f, "  {}: {:.3}", percentile, value)?;
+    }
+
+    Ok(())
+}
+
+/// Format rates only sampled by some workloads, if any
+fn fmt_optional_rates(
+    f: &mut std::fmt::Formatter<'_>,
+    rates: [(&str, &Option<Metric>); 3],
+) -> std::fmt::Result {
+    for (name, rate) in rates {
+        if let Some(rate) = rate {
+            writeln!(f)?;
+            fmt_rate(f, name, rate)?;
+        }
+    }
+
+    Ok(())
+}
 
+/// Format verification results, if any
+fn fmt_verification(
+    f: &mut std::fmt::Formatter<'_>,
+    verification: &Option<Verification>,
+) -> std::fmt::Result {
+    if let Some(verification) = verification {
         writeln!(f)?;
-        writeln!(f, "Latency:")?;
-        writeln!(f, "  num_samples: {}", self.latency.num_samples)?;
-        writeln!(
-            f,
-            "  min: {}",
-            humantime::format_duration(Duration::from_micros(self.latency.min as u64))
-        )?;
-        writeln!(
-            f,
-            "  max: {}",
-            humantime::format_duration(Duration::from_micros(self.latency.max as u64))
-        )?;
-        writeln!(
-            f,
-            "  avg: {}",
-            humantime::format_duration(Duration::from_micros(self.latency.avg as u64))
-        )?;
-        writeln!(
-            f,
-            "  stdev: {}",
-            humantime::format_duration(Duration::from_micros(self.latency.stdev as u64))
-        )?;
-        writeln!(
-            f,
-            "  p99: {}",
-            humantime::format_duration(Duration::from_micros(self.latency.p99 as u64))
-        )?;
-        writeln!(
+        writeln!(f, "Verification:")?;
+        writeln!(f, "  verified: {}", verification.verified)?;
+        writeln!(f, "  mismatches: {}", verification.mismatches)?;
+    }
+
+    Ok(())
+}
+
+/// Format metrics of a part of operations
+fn fmt_op_metrics(f: &mut std::fmt::Formatter<'_>, metrics: &OpMetrics) -> std::fmt::Result {
+    writeln!(f)?;
+    fmt_bandwidth(f, "Bandwidth", &metrics.bandwidth)?;
+
+    writeln!(f)?;
+    fmt_latency(f, "Latency", &metrics.latency)?;
+    if let Some(part_latency) = &metrics.part_latency {
+        writeln!(f)?;
+        fmt_latency(f, "Part latency", part_latency)?;
+    }
+    if let Some(ttfb) = &metrics.ttfb {
+        writeln!(f)?;
+        fmt_latency(f, "Time to first byte", ttfb)?;
+    }
+
+    writeln!(f)?;
+    fmt_rate(f, "IOPS", &metrics.iops)?;
+    fmt_optional_rates(
+        f,
+        [
+            ("Entries per second", &metrics.entries),
+            ("Estimated pages per second", &metrics.estimated_pages),
+            ("Deleted objects per second", &metrics.deleted),
+        ],
+    )?;
+    fmt_verification(f, &metrics.verification)
+}
+
+/// Format latency in microseconds
+fn fmt_micros(micros: f64) -> String {
+    humantime::format_duration(Duration::from_micros(micros as u64)).to_string()
+}
+
+/// Format metrics of each interval as a table, with a column of latency for
+/// each of `percentiles`
+fn fmt_series(
+    f: &mut std::fmt::Formatter<'_>,
+    series: &[IntervalMetrics],
+    percentiles: &[Percentile],
+) -> std::fmt::Result {
+    writeln!(f, "Time series:")?;
+    write!(
+        f,
+        "  {:>8}  {:>14}  {:>12}  {:>6}",
+        "time", "throughput", "iops", "errors"
+    )?;
+    for percentile in percentiles {
+        write!(f, "  {:>12}", percentile.to_string())?;
+    }
+    writeln!(f, "  {:>12}", "max")?;
+
+    for interval in series {
+        write!(
             f,
-            "  p95: {}",
-            humantime::format_duration(Duration::from_micros(self.latency.p95 as u64))
+            "  {:>8}  {:>14}  {:>12.3}  {:>6}",
+            humantime::format_duration(Duration::from_millis((interval.start * 1000.0) as u64))
+                .to_string(),
+            format!(
+                "{}/s",
+                humansize::format_size(interval.throughput as u64, humansize::BINARY)
+            ),
+            interval.iops,
+            interval.errors,
         )?;
-        writeln!(
+        for percentile in percentiles {
+            let value = interval
+                .latency
+                .as_ref()
+                .and_then(|latency| latency.percentiles.get(percentile));
+            write!(
+                f,
+                "  {:>12}",
+                value.map_or("-".to_string(), |v| fmt_micros(*v))
+            )?;
+        }
+        let max = interval.latency.as_ref().map(|latency| latency.max);
+        writeln!(f, "  {:>12}", max.map_or("-".to_string(), fmt_micros))?;
+    }
+
+    Ok(())
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Number of parallel jobs: {}", self.num_jobs)?;
+        writeln!(f, "File size: {}", self.file_size)?;
+        writeln!(f, "Workload: {}", self.workload)?;
+        writeln!(f, "Payload seed: {}", self.seed)?;
+        if self.errors > 0 {
+            writeln!(f, "Errors: {}", self.errors)?;
+        }
+        if self.delete_fallbacks > 0 {
+            writeln!(
+                f,
+                "Deletes falling back to writes: {}",
+                self.delete_fallbacks
+            )?;
+        }
+
+        writeln!(f)?;
+        fmt_throughput(f, &self.throughput)?;
+
+        writeln!(f)?;
+        fmt_bandwidth(f, "Bandwidth", &self.bandwidth)?;
+
+        writeln!(f)?;
+        fmt_latency(f, "Latency", &self.latency)?;
+        if let Some(part_latency) = &self.part_latency {
+            writeln!(f)?;
+            fmt_latency(f, "Part latency", part_latency)?;
+        }
+        if let Some(ttfb) = &self.ttfb {
+            writeln!(f)?;
+            fmt_latency(f, "Time to first byte", ttfb)?;
+        }
+
+        writeln!(f)?;
+        fmt_rate(f, "IOPS", &self.iops)?;
+        fmt_optional_rates(
             f,
-            "  p50: {}",
-            humantime::format_duration(Duration::from_micros(self.latency.p50 as u64))
+            [
+                ("Entries per second", &self.entries),
+                ("Estimated pages per second", &self.estimated_pages),
+                ("Deleted objects per second", &self.deleted),
+            ],
         )?;
+        fmt_verification(f, &self.verification)?;
+
+        for (op, metrics) in &self.operations {
+            writeln!(f)?;
+            writeln!(f, "Operation: {}", op)?;
+            fmt_op_metrics(f, metrics)?;
+        }
+
+        for size in &self.sizes {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "Object size: ({}, {}]",
+                humansize::format_size(size.min_size, humansize::BINARY),
+                humansize::format_size(size.max_size, humansize::BINARY)
+            )?;
+            fmt_op_metrics(f, &size.metrics)?;
+        }
 
         writeln!(f)?;
-        writeln!(f, "IOPS:")?;
-        writeln!(f, "  num_samples: {:.3}", self.iops.num_samples)?;
-        writeln!(f, "  min: {:.3}", self.iops.min)?;
-        writeln!(f, "  max: {:.3}", self.iops.max)?;
-        writeln!(f, "  avg: {:.3}", self.iops.avg)?;
-        writeln!(f, "  stdev: {:.3}", self.iops.stdev)?;
-        writeln!(f, "  p99: {:.3}", self.iops.p99)?;
-        writeln!(f, "  p95: {:.3}", self.iops.p95)?;
-        writeln!(f, "  p50: {:.3}", self.iops.p50)?;
-
-        Ok(())
+        let percentiles: Vec<Percentile> = self.latency.percentiles.keys().copied().collect();
+        fmt_series(f, &self.series, &percentiles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::*;
+    use crate::{config::Size, sample::DEFAULT_PRECISION};
+
+    /// Stats of 4KiB operations of a job that ran for 3.5s in intervals of
+    /// 1s, so the last interval is cut short. The second interval has a
+    /// failed operation and no completed one.
+    fn job_stats(percentiles: &[f64]) -> JobStats {
+        let mut stats = JobStats::new(DEFAULT_PRECISION);
+        stats.elapsed = Duration::from_millis(3500);
+        stats.interval = Duration::from_secs(1);
+        stats.percentiles = percentiles.to_vec();
+        for (errors, latencies) in [
+            (0, vec![100.0, 200.0]),
+            (1, vec![]),
+            (0, vec![300.0, 400.0, 500.0, 600.0]),
+            (0, vec![700.0]),
+        ] {
+            let mut interval_latency = SampleSet::new(DEFAULT_PRECISION);
+            for latency in &latencies {
+                stats.total.latency.add(*latency);
+                stats.total.bandwidth.add(4096.0 / latency * 1e6);
+                interval_latency.add(*latency);
+            }
+            stats.total.counts.push(Counts {
+                ops: latencies.len() as u64,
+                bytes: 4096 * latencies.len() as u64,
+                errors,
+                deleted: 0,
+            });
+            stats
+                .latency_per_interval
+                .push(Metric::new_if_sampled(&interval_latency, percentiles));
+        }
+        stats
+    }
+
+    #[test]
+    fn test_rate_metric() {
+        // The last interval is cut short and left out
+        let stats = job_stats(&[50.0]);
+        let iops = rate_metric(&stats.total.counts, |counts| counts.ops, &stats);
+        assert_snapshot!(serde_json::to_string_pretty(&iops).unwrap(), @r###"
+        {
+          "num_samples": 3,
+          "min": 0.0,
+          "max": 4.0,
+          "avg": 2.0,
+          "stdev": 1.6334014271520099,
+          "percentiles": {
+            "p50": 2.0
+          }
+        }
+        "###);
+
+        // Shorter than an interval
+        let mut stats = job_stats(&[50.0]);
+        stats.elapsed = Duration::from_millis(500);
+        stats.total.counts.truncate(1);
+        let iops = rate_metric(&stats.total.counts, |counts| counts.ops, &stats);
+        assert_snapshot!(serde_json::to_string_pretty(&iops).unwrap(), @r###"
+        {
+          "num_samples": 1,
+          "min": 4.0,
+          "max": 4.0,
+          "avg": 4.0,
+          "stdev": 0.0,
+          "percentiles": {
+            "p50": 4.0
+          }
+        }
+        "###);
+    }
+
+    #[test]
+    fn test_throughput() {
+        let throughput = Throughput::new(&job_stats(&[50.0]));
+        assert_snapshot!(serde_json::to_string_pretty(&throughput).unwrap(), @r###"
+        {
+          "bytes": 28672,
+          "elapsed": 3.5,
+          "avg": 8192.0,
+          "per_interval": {
+            "num_samples": 3,
+            "min": 0.0,
+            "max": 16384.0,
+            "avg": 8192.0,
+            "stdev": 6690.412175957671,
+            "percentiles": {
+              "p50": 8196.095
+            }
+          }
+        }
+        "###);
+    }
+
+    #[test]
+    fn test_interval_series() {
+        let series = IntervalMetrics::new_series(&job_stats(&[50.0]));
+        assert_snapshot!(serde_json::to_string_pretty(&series).unwrap(), @r###"
+        [
+          {
+            "start": 0.0,
+            "throughput": 8192.0,
+            "iops": 2.0,
+            "errors": 0,
+            "latency": {
+              "num_samples": 2,
+              "min": 100.0,
+              "max": 200.0,
+              "avg": 150.0,
+              "stdev": 50.0,
+              "percentiles": {
+                "p50": 100.031
+              }
+            }
+          },
+          {
+            "start": 1.0,
+            "throughput": 0.0,
+            "iops": 0.0,
+            "errors": 1
+          },
+          {
+            "start": 2.0,
+            "throughput": 16384.0,
+            "iops": 4.0,
+            "errors": 0,
+            "latency": {
+              "num_samples": 4,
+              "min": 300.0,
+              "max": 600.0,
+              "avg": 450.0,
+              "stdev": 111.7819820901383,
+              "percentiles": {
+                "p50": 400.127
+              }
+            }
+          },
+          {
+            "start": 3.0,
+            "throughput": 8192.0,
+            "iops": 2.0,
+            "errors": 0,
+            "latency": {
+              "num_samples": 1,
+              "min": 700.0,
+              "max": 700.0,
+              "avg": 700.0,
+              "stdev": 0.0,
+              "percentiles": {
+                "p50": 700.0
+              }
+            }
+          }
+        ]
+        "###);
+    }
+
+    #[test]
+    fn test_report_percentiles() {
+        let report = Report::new(
+            2,
+            FileSize::Fixed(Size(4096)),
+            "upload".to_string(),
+            42,
+            job_stats(&[50.0, 99.9]),
+        );
+        assert_snapshot!(report.to_string(), @r###"
+        Number of parallel jobs: 2
+        File size: 4KiB
+        Workload: upload
+        Payload seed: 42
+        Errors: 1
+
+        Throughput:
+          total: 28 KiB in 3s 500ms
+          avg: 8 KiB/s
+
+        Throughput per interval:
+          num_samples: 3
+          min: 0 B/s
+          max: 16 KiB/s
+          avg: 8 KiB/s
+          stdev: 6.53 KiB/s
+          p50: 8.00 KiB/s
+          p99.9: 16 KiB/s
+
+        Bandwidth:
+          num_samples: 7
+          min: 5.58 MiB/s
+          max: 39.06 MiB/s
+          avg: 14.47 MiB/s
+          stdev: 10.96 MiB/s
+          p50: 9.77 MiB/s
+          p99.9: 39.06 MiB/s
+
+        Latency:
+          num_samples: 7
+          min: 100us
+          max: 700us
+          avg: 400us
+          stdev: 200us
+          p50: 400us
+          p99.9: 700us
+
+        IOPS:
+          num_samples: 3
+          min: 0.000
+          max: 4.000
+          avg: 2.000
+          stdev: 1.633
+          p50: 2.000
+          p99.9: 4.000
+
+        Time series:
+              time      throughput          iops  errors           p50         p99.9           max
+                0s         8 KiB/s         2.000       0         100us         200us         200us
+                1s           0 B/s         0.000       1             -             -             -
+                2s        16 KiB/s         4.000       0         400us         600us         600us
+                3s         8 KiB/s         2.000       0         700us         700us         700us
+
+        "###);
+        assert_snapshot!(serde_json::to_string_pretty(&report).unwrap(), @r###"
+        {
+          "num_jobs": 2,
+          "file_size": "4KiB",
+          "workload": "upload",
+          "seed": 42,
+          "throughput": {
+            "bytes": 28672,
+            "elapsed": 3.5,
+            "avg": 8192.0,
+            "per_interval": {
+              "num_samples": 3,
+              "min": 0.0,
+              "max": 16384.0,
+              "avg": 8192.0,
+              "stdev": 6690.412175957671,
+              "percentiles": {
+                "p50": 8196.095,
+                "p99.9": 16384.0
+              }
+            }
+          },
+          "errors": 1,
+          "delete_fallbacks": 0,
+          "bandwidth": {
+            "num_samples": 7,
+            "min": 5851428.571428572,
+            "max": 40960000.0,
+            "avg": 15171918.367346939,
+            "stdev": 11493164.737880087,
+            "percentiles": {
+              "p50": 10242490.367,
+              "p99.9": 40960000.0
+            }
+          },
+          "latency": {
+            "num_samples": 7,
+            "min": 100.0,
+            "max": 700.0,
+            "avg": 400.0,
+            "stdev": 200.02059920693677,
+            "percentiles": {
+              "p50": 400.127,
+              "p99.9": 700.0
+            }
+          },
+          "iops": {
+            "num_samples": 3,
+            "min": 0.0,
+            "max": 4.0,
+            "avg": 2.0,
+            "stdev": 1.6334014271520099,
+            "percentiles": {
+              "p50": 2.0,
+              "p99.9": 4.0
+            }
+          },
+          "interval": 1.0,
+          "series": [
+            {
+              "start": 0.0,
+              "throughput": 8192.0,
+              "iops": 2.0,
+              "errors": 0,
+              "latency": {
+                "num_samples": 2,
+                "min": 100.0,
+                "max": 200.0,
+                "avg": 150.0,
+                "stdev": 50.0,
+                "percentiles": {
+                  "p50": 100.031,
+                  "p99.9": 200.0
+                }
+              }
+            },
+            {
+              "start": 1.0,
+              "throughput": 0.0,
+              "iops": 0.0,
+              "errors": 1
+            },
+            {
+              "start": 2.0,
+              "throughput": 16384.0,
+              "iops": 4.0,
+              "errors": 0,
+              "latency": {
+                "num_samples": 4,
+                "min": 300.0,
+                "max": 600.0,
+                "avg": 450.0,
+                "stdev": 111.7819820901383,
+                "percentiles": {
+                  "p50": 400.127,
+                  "p99.9": 600.0
+                }
+              }
+            },
+            {
+              "start": 3.0,
+              "throughput": 8192.0,
+              "iops": 2.0,
+              "errors": 0,
+              "latency": {
+                "num_samples": 1,
+                "min": 700.0,
+                "max": 700.0,
+                "avg": 700.0,
+                "stdev": 0.0,
+                "percentiles": {
+                  "p50": 700.0,
+                  "p99.9": 700.0
+                }
+              }
+            }
+          ]
+        }
+        "###);
     }
 }
@@ -1,34 +1,81 @@
-#[derive(Debug, Clone, Default)]
-pub struct SampleSet(pub Vec<f64>);
+use hdrhistogram::Histogram;
+
+/// Default number of significant decimal digits kept by samples
+pub const DEFAULT_PRECISION: u8 = 3;
+
+/// Samples are recorded as integers in units of 1/SCALE, which keeps three
+/// decimal places of fractional samples like rates
+const SCALE: f64 = 1000.0;
+
+/// Largest value a histogram can grow to track
+const MAX_VALUE: u64 = i64::MAX as u64 / 2;
+
+/// Set of samples kept in a HDR histogram, which takes memory bounded by the
+/// range of values rather than the number of samples. Samples are recorded in
+/// constant time and kept with `precision` significant decimal digits, while
+/// min, max and average are exact.
+#[derive(Debug, Clone)]
+pub struct SampleSet {
+    histogram: Histogram<u64>,
+    min: f64,
+    max: f64,
+    sum: f64,
+}
+
+impl Default for SampleSet {
+    fn default() -> Self {
+        Self::new(DEFAULT_PRECISION)
+    }
+}
 
 impl SampleSet {
+    /// Create an empty sample set keeping `precision` significant decimal
+    /// digits, which is between 0 and 5
+    pub fn new(precision: u8) -> Self {
+        Self {
+            histogram: Histogram::new(precision).expect("precision is between 0 and 5"),
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            sum: 0.0,
+        }
+    }
+
     /// Add a new sample
     pub fn add(&mut self, sample: f64) {
-        self.0.push(sample);
+        // Negative and NaN samples saturate to 0
+        let value = ((sample * SCALE).round() as u64).min(MAX_VALUE);
+        self.histogram
+            .record(value)
+            .expect("histogram grows to track values up to MAX_VALUE");
+        self.min = self.min.min(sample);
+        self.max = self.max.max(sample);
+        self.sum += sample;
     }
 
     /// Merge two sample set
     pub fn merge(mut self, other: SampleSet) -> Self {
-        self.0.extend(other.0);
+        self.histogram
+            .add(&other.histogram)
+            .expect("histogram grows to track values up to MAX_VALUE");
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
         self
     }
 
     /// Get number of samples
     pub fn num_samples(&self) -> usize {
-        self.0.len()
+        self.histogram.len() as usize
     }
 
     /// Get min value
     pub fn min(&self) -> f64 {
-        self.0.iter().copied().fold(f64::INFINITY, |a, b| a.min(b))
+        self.min
     }
 
     /// Get max value
     pub fn max(&self) -> f64 {
-        self.0
-            .iter()
-            .copied()
-            .fold(f64::NEG_INFINITY, |a, b| a.max(b))
+        self.max
     }
 
     /// Get average value
@@ -36,7 +83,7 @@ impl SampleSet {
         if self.num_samples() == 0 {
             return f64::NAN;
         }
-        self.0.iter().copied().sum::<f64>() / self.0.len() as f64
+        self.sum / self.num_samples() as f64
     }
 
     /// Get standard deviation
@@ -44,14 +91,7 @@ impl SampleSet {
         if self.num_samples() == 0 {
             return f64::NAN;
         }
-        let avg = self.avg();
-        let sum = self
-            .0
-            .iter()
-            .copied()
-            .map(|x| (x - avg).powi(2))
-            .sum::<f64>();
-        (sum / self.0.len() as f64).sqrt()
+        self.histogram.stdev() / SCALE
     }
 
     /// Get percentile value
@@ -59,11 +99,56 @@ impl SampleSet {
         if self.num_samples() == 0 {
             return f64::NAN;
         }
-        let mut sorted = self.0.clone();
-        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        sorted
-            .get(((sorted.len() - 1) as f64 * percentile / 100.0) as usize)
-            .copied()
-            .unwrap_or(f64::NAN)
+        let value = self.histogram.value_at_quantile(percentile / 100.0) as f64 / SCALE;
+        // Values of the histogram are rounded to its precision
+        value.clamp(self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_set() {
+        let mut samples = SampleSet::default();
+        for i in 1..=10000 {
+            samples.add(i as f64);
+        }
+        assert_eq!(samples.num_samples(), 10000);
+        assert_eq!(samples.min(), 1.0);
+        assert_eq!(samples.max(), 10000.0);
+        assert_eq!(samples.avg(), 5000.5);
+        assert!((samples.stdev() - 2886.75).abs() < 3.0);
+        for p in [50.0, 90.0, 99.0, 99.9, 99.99] {
+            let expected = 100.0 * p;
+            let actual = samples.percentile(p);
+            assert!(
+                (actual - expected).abs() <= expected / 1000.0,
+                "{p}: {actual}"
+            );
+        }
+        assert_eq!(samples.percentile(100.0), 10000.0);
+
+        let mut rates = SampleSet::default();
+        rates.add(0.25);
+        rates.add(0.5);
+        assert_eq!(rates.percentile(50.0), 0.25);
+    }
+
+    #[test]
+    fn test_merge_sample_sets() {
+        let mut small = SampleSet::default();
+        let mut large = SampleSet::default();
+        for i in 0..1000 {
+            small.add(i as f64);
+            large.add(1_000_000.0 + i as f64);
+        }
+        let merged = small.clone().merge(large.clone());
+        assert_eq!(merged.num_samples(), 2000);
+        assert_eq!(merged.min(), 0.0);
+        assert_eq!(merged.max(), 1_000_999.0);
+        assert_eq!(merged.percentile(25.0), small.percentile(50.0));
+        assert_eq!(merged.percentile(75.0), large.percentile(50.0));
     }
 }
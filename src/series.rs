@@ -0,0 +1,150 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::{job::interval_index, report::Metric, sample::SampleSet};
+
+/// Latency of operations handed over by workers, which is summarized once
+/// all workers have moved past its interval
+#[derive(Debug)]
+struct Collector {
+    percentiles: Vec<f64>,
+    /// Index of the first interval each worker has not handed over yet
+    progress: Vec<usize>,
+    /// Merged latency of intervals not summarized yet
+    pending: BTreeMap<usize, SampleSet>,
+    intervals: Vec<Option<Metric>>,
+}
+
+impl Collector {
+    /// Summarize intervals before `end`, including the ones without any
+    /// operation
+    fn summarize_until(&mut self, end: usize) {
+        while self.intervals.len() < end {
+            let index = self.intervals.len();
+            let latency = self
+                .pending
+                .remove(&index)
+                .and_then(|latency| Metric::new_if_sampled(&latency, &self.percentiles));
+            self.intervals.push(latency);
+        }
+    }
+
+    /// End of intervals that have pending latency
+    fn pending_end(&self) -> usize {
+        self.pending
+            .last_key_value()
+            .map_or(self.intervals.len(), |(index, _)| index + 1)
+    }
+}
+
+/// Time series of latency of operations completed by all workers in each
+/// interval since the job started, while the other numbers of each interval
+/// are counted by `OpStats::counts`. Each worker records latency into a
+/// sample set of its own, and hands it over once it moves to a later
+/// interval. Sample sets are merged and summarized once all workers have
+/// moved past their interval, so the series is only locked once per interval
+/// by each worker, and memory does not grow with the length of the run.
+#[derive(Debug, Clone)]
+pub struct TimeSeries {
+    interval: Duration,
+    precision: u8,
+    collector: Arc<Mutex<Collector>>,
+}
+
+impl TimeSeries {
+    pub fn new(
+        interval: Duration,
+        num_workers: usize,
+        precision: u8,
+        percentiles: Vec<f64>,
+    ) -> Self {
+        Self {
+            interval,
+            precision,
+            collector: Arc::new(Mutex::new(Collector {
+                percentiles,
+                progress: vec![0; num_workers],
+                pending: BTreeMap::new(),
+                intervals: vec![],
+            })),
+        }
+    }
+
+    /// Recorder of operations of the `worker`-th worker
+    pub fn recorder(&self, worker: usize) -> Recorder {
+        Recorder {
+            series: self.clone(),
+            worker,
+            index: 0,
+            latency: None,
+        }
+    }
+
+    /// Summarize latency of all intervals once all workers finished, the
+    /// last of which is the partial one the job ends in after `elapsed`
+    pub fn finish(&self, elapsed: Duration) -> Vec<Option<Metric>> {
+        let mut collector = self.collector.lock().unwrap();
+        let end = collector
+            .pending_end()
+            .max(interval_index(elapsed, self.interval) + 1);
+        collector.summarize_until(end);
+        std::mem::take(&mut collector.intervals)
+    }
+}
+
+/// Records latency of operations of a worker in the current interval, which
+/// is handed over to the series once the worker moves to a later interval or
+/// stops
+#[derive(Debug)]
+pub struct Recorder {
+    series: TimeSeries,
+    worker: usize,
+    /// Index of the current interval
+    index: usize,
+    latency: Option<SampleSet>,
+}
+
+impl Recorder {
+    /// Record an operation that took `latency`, completed in the `index`-th
+    /// interval
+    pub fn add(&mut self, index: usize, latency: Duration) {
+        if index > self.index {
+            self.hand_over(index);
+        }
+        let precision = self.series.precision;
+        self.latency
+            .get_or_insert_with(|| SampleSet::new(precision))
+            .add(latency.as_micros() as f64);
+    }
+
+    /// Hand over latency of the current interval and move to the `next`
+    /// interval, then summarize intervals all workers have moved past
+    fn hand_over(&mut self, next: usize) {
+        let mut collector = self.series.collector.lock().unwrap();
+        if let Some(latency) = self.latency.take() {
+            let merged = match collector.pending.remove(&self.index) {
+                Some(merged) => merged.merge(latency),
+                None => latency,
+            };
+            collector.pending.insert(self.index, merged);
+        }
+        collector.progress[self.worker] = next;
+        self.index = next;
+
+        let end = match collector.progress.iter().min() {
+            Some(&usize::MAX) | None => collector.pending_end(),
+            Some(&end) => end,
+        };
+        collector.summarize_until(end);
+    }
+}
+
+impl Drop for Recorder {
+    /// Hand over latency of the last interval once the worker stops
+    fn drop(&mut self) {
+        self.hand_over(usize::MAX);
+    }
+}